@@ -0,0 +1,115 @@
+use std::fmt::{Display, Formatter, Write};
+
+use crate::{render::Renderer, utils::Grid2D, vec::Vec2i};
+
+pub enum Answer {
+    Int(i64),
+    List(Vec<Answer>),
+    Point(Vec2i),
+    Grid(Grid2D<char>),
+}
+
+impl Answer {
+    /// Multi-line form of the answer, meant for reading in the terminal.
+    /// The submission string is the [`Display`] form.
+    pub fn pretty(&self) -> String {
+        match self {
+            Answer::Int(_) => self.to_string(),
+            Answer::Point(p) => p.to_string(),
+            Answer::List(items) => {
+                let mut out = String::new();
+                for (i, item) in items.iter().enumerate() {
+                    let pretty = item.pretty();
+                    let mut lines = pretty.lines();
+                    writeln!(out, "{i:>3}: {}", lines.next().unwrap_or_default()).unwrap();
+                    for l in lines {
+                        writeln!(out, "     {l}").unwrap();
+                    }
+                }
+                out.pop();
+                out
+            }
+            Answer::Grid(grid) => {
+                let mut out = Renderer::new(grid).plain(true).to_string();
+                out.pop();
+                out
+            }
+        }
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(value: i64) -> Self {
+        Self::Int(value)
+    }
+}
+impl From<i32> for Answer {
+    fn from(value: i32) -> Self {
+        Self::Int(value as _)
+    }
+}
+impl From<usize> for Answer {
+    fn from(value: usize) -> Self {
+        Self::Int(value as _)
+    }
+}
+impl From<u64> for Answer {
+    fn from(value: u64) -> Self {
+        Self::Int(value as _)
+    }
+}
+impl From<Vec2i> for Answer {
+    fn from(value: Vec2i) -> Self {
+        Self::Point(value)
+    }
+}
+impl From<Grid2D<char>> for Answer {
+    fn from(value: Grid2D<char>) -> Self {
+        Self::Grid(value)
+    }
+}
+impl<T: Into<Answer>> From<Vec<T>> for Answer {
+    fn from(value: Vec<T>) -> Self {
+        Self::List(value.into_iter().map(Into::into).collect())
+    }
+}
+
+/// The canonical submission string: integers as is, points as `x,y`,
+/// lists comma-joined and grids as their rows separated by newlines.
+impl Display for Answer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Int(val) => val.fmt(f),
+            Answer::Point(p) => write!(f, "{},{}", p.x, p.y),
+            Answer::List(items) => {
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        f.write_char(',')?;
+                    }
+                    item.fmt(f)?;
+                }
+                Ok(())
+            }
+            Answer::Grid(grid) => {
+                for (y, row) in grid.rows().enumerate() {
+                    if y > 0 {
+                        f.write_char('\n')?;
+                    }
+                    for &c in row {
+                        f.write_char(c)?;
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+#[test]
+fn test_answer_formats() {
+    let grid: Grid2D<char> = "#.\n.#\n".parse().unwrap();
+    let list: Answer = vec![Answer::from(1), Vec2i::new(2, 3).into(), grid.into()].into();
+
+    assert_eq!(list.to_string(), "1,2,3,#.\n.#");
+    assert_eq!(list.pretty(), "  0: 1\n  1: (2, 3)\n  2: #.\n     .#");
+}
@@ -0,0 +1,196 @@
+use std::{collections::HashMap, hash::Hash, mem};
+
+use crate::{utils::Grid2D, vec::Vec2i};
+
+/// What the cells next to the border see beyond it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Edge<T> {
+    /// Cells with the given value.
+    Dead(T),
+    /// The cells on the opposite side.
+    Wrap,
+    /// The nearest cell on the border itself.
+    Clamp,
+}
+
+/// A repeated sighting of the same generation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    /// First generation that is part of the cycle.
+    pub start: usize,
+    /// Generations until it repeats, 1 for a fixed point.
+    pub period: usize,
+}
+
+/// Steps every cell of a grid at once with `rule(cell, neighbours)`. The
+/// next generation is written into a second grid that is swapped in, so
+/// stepping does not allocate.
+pub struct Automaton<T, R> {
+    current: Grid2D<T>,
+    next: Grid2D<T>,
+    offsets: Vec<Vec2i>,
+    edge: Edge<T>,
+    rule: R,
+    neighbors: Vec<T>,
+    generation: usize,
+}
+
+impl<T, R> Automaton<T, R>
+where
+    T: Clone,
+    R: FnMut(&T, &[T]) -> T,
+{
+    /// Uses the 8 surrounding cells unless changed.
+    pub fn new(grid: Grid2D<T>, edge: Edge<T>, rule: R) -> Self {
+        Self {
+            next: grid.clone(),
+            current: grid,
+            offsets: Vec2i::directions_8().collect(),
+            edge,
+            rule,
+            neighbors: Vec::new(),
+            generation: 0,
+        }
+    }
+
+    /// The offsets of the cells passed to the rule, in this order, e.g.
+    /// [`Vec2i::directions_4`].
+    pub fn neighborhood(mut self, offsets: impl IntoIterator<Item = Vec2i>) -> Self {
+        self.offsets = offsets.into_iter().collect();
+        self
+    }
+
+    pub fn grid(&self) -> &Grid2D<T> {
+        &self.current
+    }
+
+    pub fn into_grid(self) -> Grid2D<T> {
+        self.current
+    }
+
+    /// Number of steps taken so far.
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    pub fn step(&mut self) {
+        let mut neighbors = mem::take(&mut self.neighbors);
+        for (pos, cell) in self.current.iter_vec2().zip(self.next.iter_mut()) {
+            neighbors.clear();
+            for &offset in &self.offsets {
+                let n = neighbor(&self.current, &self.edge, pos + offset);
+                neighbors.push(n.clone());
+            }
+            *cell = (self.rule)(&self.current[pos], &neighbors);
+        }
+        self.neighbors = neighbors;
+        mem::swap(&mut self.current, &mut self.next);
+        self.generation += 1;
+    }
+
+    /// Steps until a generation equals the one before, at most `limit`
+    /// times. Returns the generation that stayed put.
+    pub fn run_to_fixed_point(&mut self, limit: usize) -> Option<usize>
+    where
+        T: PartialEq,
+    {
+        for _ in 0..limit {
+            self.step();
+            if self.current == self.next {
+                return Some(self.generation - 1);
+            }
+        }
+        None
+    }
+
+    /// Steps until some generation shows up again, at most `limit` times.
+    /// Keeps a copy of every generation seen.
+    pub fn find_cycle(&mut self, limit: usize) -> Option<Cycle>
+    where
+        T: Eq + Hash,
+    {
+        let mut seen = HashMap::from([(self.current.clone(), self.generation)]);
+        for _ in 0..limit {
+            self.step();
+            if let Some(&start) = seen.get(&self.current) {
+                let period = self.generation - start;
+                return Some(Cycle { start, period });
+            }
+            seen.insert(self.current.clone(), self.generation);
+        }
+        None
+    }
+}
+
+/// The cell seen at `pos`, which may be any distance outside the grid.
+fn neighbor<'a, T>(grid: &'a Grid2D<T>, edge: &'a Edge<T>, pos: Vec2i) -> &'a T {
+    let (w, h) = (grid.width(), grid.height());
+    match edge {
+        Edge::Dead(dead) => grid.get(pos).unwrap_or(dead),
+        Edge::Wrap => &grid[(pos.x.rem_euclid(w), pos.y.rem_euclid(h))],
+        Edge::Clamp => &grid[(pos.x.clamp(0, w - 1), pos.y.clamp(0, h - 1))],
+    }
+}
+
+#[test]
+fn test_automaton() {
+    let life = |&alive: &bool, n: &[bool]| {
+        let count = n.iter().filter(|&&a| a).count();
+        count == 3 || (alive && count == 2)
+    };
+    let parse = |s: &str| Grid2D::parse_with(s, |c| Ok::<_, ()>(c == '#')).unwrap();
+
+    let blinker = parse(".....\n..#..\n..#..\n..#..\n.....\n");
+    let mut auto = Automaton::new(blinker.clone(), Edge::Dead(false), life);
+    auto.step();
+    assert_eq!(auto.grid(), &parse(".....\n.....\n.###.\n.....\n.....\n"));
+    assert_eq!(
+        auto.find_cycle(10),
+        Some(Cycle {
+            start: 1,
+            period: 2
+        })
+    );
+
+    let block = parse("##..\n##..\n....\n");
+    let mut auto = Automaton::new(block.clone(), Edge::Dead(false), life);
+    assert_eq!(auto.run_to_fixed_point(10), Some(0));
+    assert_eq!(auto.into_grid(), block);
+
+    // A glider on a torus comes back to where it started.
+    let glider = parse(".#....\n..#...\n###...\n......\n......\n......\n");
+    let mut auto = Automaton::new(glider, Edge::Wrap, life);
+    assert_eq!(
+        auto.find_cycle(100),
+        Some(Cycle {
+            start: 0,
+            period: 24
+        })
+    );
+
+    let corner = parse("#..\n...\n...\n");
+    let spread = |&c: &bool, n: &[bool]| c || n.contains(&true);
+    let mut auto = Automaton::new(corner, Edge::Clamp, spread).neighborhood(Vec2i::directions_4());
+    auto.step();
+    assert_eq!(auto.grid(), &parse("##.\n#..\n...\n"));
+
+    // Every cell takes the value of the one to its left, so the border
+    // column sees the dead cells and the rule always gets one neighbour.
+    let shift = |_: &bool, n: &[bool]| {
+        assert_eq!(n.len(), 1);
+        n[0]
+    };
+    let mut auto = Automaton::new(parse("#.#\n.##\n"), Edge::Dead(true), shift)
+        .neighborhood([Vec2i::new(-1, 0)]);
+    auto.step();
+    assert_eq!(auto.grid(), &parse("##.\n#.#\n"));
+
+    // Clamped edges need no dead value, so any cell type works.
+    #[derive(Clone, PartialEq)]
+    struct Cell(u8);
+    let cells = Grid2D::parse_with("19\n", |c| Ok::<_, ()>(Cell(c as u8))).unwrap();
+    let max = |c: &Cell, n: &[Cell]| n.iter().fold(c.clone(), |a, b| Cell(a.0.max(b.0)));
+    let mut auto = Automaton::new(cells, Edge::Clamp, max).neighborhood([Vec2i::new(3, 0)]);
+    auto.step();
+    assert!(auto.grid().iter().all(|c| c.0 == b'9'));
+}
@@ -0,0 +1,205 @@
+use crate::vec::Vec2i;
+
+/// A set of grid positions stored as one bit per cell, addressed like
+/// [`crate::utils::Grid2D`]. Cheaper than a `HashSet<Vec2i>` on dense grids.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitGrid {
+    width: i32,
+    height: i32,
+    words: Vec<u64>,
+}
+
+impl BitGrid {
+    pub fn new(width: i32, height: i32) -> Self {
+        let cells = (width * height) as usize;
+        Self {
+            width,
+            height,
+            words: vec![0; cells.div_ceil(64)],
+        }
+    }
+
+    pub fn width(&self) -> i32 {
+        self.width
+    }
+
+    pub fn height(&self) -> i32 {
+        self.height
+    }
+
+    fn bit<I: Into<(i32, i32)>>(&self, idx: I) -> Option<usize> {
+        let (x, y) = idx.into();
+        if x >= 0 && x < self.width && y >= 0 && y < self.height {
+            Some((y * self.width + x) as usize)
+        } else {
+            None
+        }
+    }
+
+    /// Positions outside the grid are never contained.
+    pub fn contains<I: Into<(i32, i32)>>(&self, idx: I) -> bool {
+        self.bit(idx)
+            .is_some_and(|bit| self.words[bit / 64] & (1 << (bit % 64)) != 0)
+    }
+
+    /// Returns whether `idx` was newly inserted, like `HashSet::insert`.
+    pub fn insert<I: Into<(i32, i32)>>(&mut self, idx: I) -> bool {
+        let (x, y) = idx.into();
+        let bit = self.bit((x, y)).unwrap_or_else(|| {
+            panic!(
+                "Index ({}, {}) is outside the grid dimensions ({}x{})",
+                x, y, self.width, self.height
+            )
+        });
+        let word = &mut self.words[bit / 64];
+        let mask = 1 << (bit % 64);
+        let new = *word & mask == 0;
+        *word |= mask;
+        new
+    }
+
+    /// Returns whether `idx` was set.
+    pub fn remove<I: Into<(i32, i32)>>(&mut self, idx: I) -> bool {
+        let Some(bit) = self.bit(idx) else {
+            return false;
+        };
+        let word = &mut self.words[bit / 64];
+        let mask = 1 << (bit % 64);
+        let was_set = *word & mask != 0;
+        *word &= !mask;
+        was_set
+    }
+
+    /// Unsets all cells, keeping the allocation.
+    pub fn clear(&mut self) {
+        self.words.fill(0);
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&w| w == 0)
+    }
+
+    /// The set positions in row order.
+    pub fn iter(&self) -> impl Iterator<Item = Vec2i> + '_ {
+        self.words.iter().enumerate().flat_map(move |(i, &word)| {
+            let mut rest = word;
+            std::iter::from_fn(move || {
+                if rest == 0 {
+                    return None;
+                }
+                let bit = i * 64 + rest.trailing_zeros() as usize;
+                rest &= rest - 1;
+                let bit = bit as i32;
+                Some(Vec2i::new(bit % self.width, bit / self.width))
+            })
+        })
+    }
+}
+
+/// A [`BitGrid`] with one layer per direction of [`Vec2i::directions_4`],
+/// for remembering in which directions a cell was passed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DirBitGrid {
+    /// Four bits per cell, next to each other in a row.
+    bits: BitGrid,
+}
+
+impl DirBitGrid {
+    pub fn new(width: i32, height: i32) -> Self {
+        Self {
+            bits: BitGrid::new(width * 4, height),
+        }
+    }
+
+    pub fn width(&self) -> i32 {
+        self.bits.width / 4
+    }
+
+    pub fn height(&self) -> i32 {
+        self.bits.height
+    }
+
+    fn layer(dir: Vec2i) -> i32 {
+        match (dir.x, dir.y) {
+            (0, 1) => 0,
+            (1, 0) => 1,
+            (0, -1) => 2,
+            (-1, 0) => 3,
+            _ => panic!("{dir:?} is not one of the four directions"),
+        }
+    }
+
+    /// Maps to the bit of `dir` in the cell at `pos`, keeping positions
+    /// outside the grid outside.
+    fn index(&self, pos: Vec2i, dir: Vec2i) -> (i32, i32) {
+        let x = if (0..self.width()).contains(&pos.x) {
+            pos.x * 4 + Self::layer(dir)
+        } else {
+            -1
+        };
+        (x, pos.y)
+    }
+
+    pub fn contains(&self, pos: Vec2i, dir: Vec2i) -> bool {
+        self.bits.contains(self.index(pos, dir))
+    }
+
+    pub fn insert(&mut self, pos: Vec2i, dir: Vec2i) -> bool {
+        self.bits.insert(self.index(pos, dir))
+    }
+
+    pub fn remove(&mut self, pos: Vec2i, dir: Vec2i) -> bool {
+        self.bits.remove(self.index(pos, dir))
+    }
+
+    pub fn clear(&mut self) {
+        self.bits.clear();
+    }
+
+    /// The set `(pos, dir)` pairs.
+    pub fn iter(&self) -> impl Iterator<Item = (Vec2i, Vec2i)> + '_ {
+        self.bits.iter().map(|p| {
+            let dir = Vec2i::directions_4().nth((p.x % 4) as usize).unwrap();
+            (Vec2i::new(p.x / 4, p.y), dir)
+        })
+    }
+
+    /// The cells passed in any direction.
+    pub fn positions(&self) -> BitGrid {
+        let mut cells = BitGrid::new(self.width(), self.height());
+        for (pos, _) in self.iter() {
+            cells.insert(pos);
+        }
+        cells
+    }
+}
+
+#[test]
+fn test_bit_grid() {
+    let mut bits = BitGrid::new(10, 10);
+    assert!(bits.insert((3, 7)));
+    assert!(!bits.insert(Vec2i::new(3, 7)));
+    assert!(bits.insert((9, 9)));
+    assert!(!bits.contains((10, 0)) && !bits.contains((-1, 0)));
+    assert_eq!(
+        bits.iter().collect::<Vec<_>>(),
+        [Vec2i::new(3, 7), Vec2i::new(9, 9)]
+    );
+    assert!(bits.remove((9, 9)));
+    assert_eq!(bits.len(), 1);
+    bits.clear();
+    assert!(bits.is_empty());
+
+    let mut dirs = DirBitGrid::new(3, 3);
+    let (pos, up) = (Vec2i::new(2, 1), Vec2i::new(0, -1));
+    assert!(dirs.insert(pos, up));
+    assert!(!dirs.contains(pos, up.rot_x2y()));
+    assert!(!dirs.contains(Vec2i::new(3, 1), up));
+    dirs.insert(pos, up.rot_x2y());
+    assert_eq!(dirs.iter().count(), 2);
+    assert_eq!(dirs.positions().len(), 1);
+}
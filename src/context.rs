@@ -0,0 +1,255 @@
+use std::{
+    any::Any,
+    collections::HashMap,
+    fmt::Display,
+    str::FromStr,
+    sync::{Arc, Mutex, OnceLock},
+    time::{Duration, Instant},
+};
+
+use crate::{frames::Frame, progress::Progress, SolveError};
+
+/// A puzzle constant that can be changed at runtime, declared by
+/// [`crate::Solution::params`].
+#[derive(Clone)]
+pub struct Param {
+    pub name: &'static str,
+    pub default: String,
+    check: fn(&str) -> Result<(), String>,
+}
+
+impl Param {
+    /// The type of `default` is the type the value has to parse as.
+    pub fn new<T>(name: &'static str, default: T) -> Self
+    where
+        T: FromStr + Display,
+        T::Err: Display,
+    {
+        Self {
+            name,
+            default: default.to_string(),
+            check: |value| value.parse::<T>().map(|_| ()).map_err(|e| e.to_string()),
+        }
+    }
+}
+
+struct ParamValue {
+    param: Param,
+    value: String,
+}
+
+type SharedValue = Arc<dyn Any + Send + Sync>;
+
+/// Running totals of [`Context::shared`] calls, so the runner can tell the
+/// shared work apart from a part's own.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct SharedUse {
+    /// Time spent computing shared values.
+    pub computed: Duration,
+    /// Number of calls that found the value already computed.
+    pub reused: usize,
+}
+
+/// Handed to every part, gives access to the parameters in effect, to
+/// values shared between the parts, to progress reporting and to the
+/// frame sink.
+#[derive(Default)]
+pub struct Context {
+    params: Vec<ParamValue>,
+    shared: Mutex<HashMap<&'static str, Arc<OnceLock<SharedValue>>>>,
+    shared_use: Mutex<SharedUse>,
+    progress: Arc<Progress>,
+    /// `None` unless the runner asked for frames.
+    frames: Option<Mutex<Vec<Frame>>>,
+}
+
+impl Context {
+    pub fn new(params: Vec<Param>) -> Self {
+        let params = params
+            .into_iter()
+            .map(|param| ParamValue {
+                value: param.default.clone(),
+                param,
+            })
+            .collect();
+        Self {
+            params,
+            shared: Mutex::default(),
+            shared_use: Mutex::default(),
+            progress: Arc::default(),
+            frames: None,
+        }
+    }
+
+    /// Overrides a declared parameter, the value is checked against its type.
+    pub fn set_param(&mut self, name: &str, value: &str) -> Result<(), SolveError> {
+        let param = self
+            .params
+            .iter_mut()
+            .find(|p| p.param.name == name)
+            .ok_or_else(|| SolveError::new(format!("unknown parameter `{name}`")))?;
+
+        (param.param.check)(value).map_err(|e| {
+            SolveError::new(format!(
+                "invalid value {value:?} for parameter `{name}`: {e}"
+            ))
+        })?;
+        param.value = value.to_owned();
+        Ok(())
+    }
+
+    /// Builder form of [`Context::set_param`] for tests, panics on errors.
+    pub fn with_param(mut self, name: &str, value: impl Display) -> Self {
+        if let Err(e) = self.set_param(name, &value.to_string()) {
+            panic!("{e}");
+        }
+        self
+    }
+
+    pub fn param<T>(&self, name: &str) -> Result<T, SolveError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let param = self
+            .params
+            .iter()
+            .find(|p| p.param.name == name)
+            .ok_or_else(|| SolveError::new(format!("unknown parameter `{name}`")))?;
+
+        param
+            .value
+            .parse()
+            .map_err(|e| SolveError::new(format!("invalid value for parameter `{name}`: {e}")))
+    }
+
+    /// Returns the value stored under `key`, computing it first if no part
+    /// did so yet. Lets part two reuse intermediate results of part one.
+    /// Only one caller computes the value, others asking meanwhile wait for
+    /// it. The time it takes is tracked apart, see [`SharedUse`].
+    pub fn shared<T, F>(&self, key: &'static str, compute: F) -> Arc<T>
+    where
+        T: Send + Sync + 'static,
+        F: FnOnce() -> T,
+    {
+        let cell = self.shared.lock().unwrap().entry(key).or_default().clone();
+        let mut computed = false;
+        let value = cell.get_or_init(|| {
+            computed = true;
+            let start = Instant::now();
+            let value = Arc::new(compute());
+            self.shared_use.lock().unwrap().computed += start.elapsed();
+            value
+        });
+        if !computed {
+            self.shared_use.lock().unwrap().reused += 1;
+        }
+        value
+            .clone()
+            .downcast()
+            .unwrap_or_else(|_| panic!("shared value `{key}` was stored with another type"))
+    }
+
+    pub(crate) fn shared_use(&self) -> SharedUse {
+        *self.shared_use.lock().unwrap()
+    }
+
+    pub fn progress(&self) -> &Progress {
+        &self.progress
+    }
+
+    pub(crate) fn progress_handle(&self) -> Arc<Progress> {
+        self.progress.clone()
+    }
+
+    /// Starts collecting the frames pushed by [`Context::frame`].
+    pub fn record_frames(&mut self) {
+        self.frames = Some(Mutex::default());
+    }
+
+    pub fn frames_enabled(&self) -> bool {
+        self.frames.is_some()
+    }
+
+    /// Pushes the frame built by `draw`. Unless frames are recorded `draw`
+    /// is never called, so snapshots cost nothing in normal runs.
+    pub fn frame<F>(&self, draw: F)
+    where
+        F: FnOnce() -> Frame,
+    {
+        if let Some(frames) = &self.frames {
+            frames.lock().unwrap().push(draw());
+        }
+    }
+
+    /// Frames pushed since the last call.
+    pub(crate) fn take_frames(&self) -> Vec<Frame> {
+        match &self.frames {
+            Some(frames) => std::mem::take(&mut *frames.lock().unwrap()),
+            None => Vec::new(),
+        }
+    }
+
+    pub fn has_param(&self, name: &str) -> bool {
+        self.params.iter().any(|p| p.param.name == name)
+    }
+
+    /// Parameters whose value differs from the declared default.
+    pub fn overridden(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.params
+            .iter()
+            .filter(|p| p.value != p.param.default)
+            .map(|p| (p.param.name, p.value.as_str()))
+    }
+}
+
+#[test]
+fn test_params() {
+    let mut ctx = Context::new(vec![
+        Param::new("blinks", 25),
+        Param::new("word", "XMAS".to_owned()),
+    ]);
+    assert_eq!(ctx.param::<u32>("blinks").unwrap(), 25);
+
+    assert!(ctx.set_param("blinks", "six").is_err());
+    assert!(ctx.set_param("steps", "6").is_err());
+    ctx.set_param("blinks", "6").unwrap();
+
+    assert_eq!(ctx.param::<u32>("blinks").unwrap(), 6);
+    assert_eq!(ctx.param::<String>("word").unwrap(), "XMAS");
+    assert_eq!(ctx.overridden().collect::<Vec<_>>(), [("blinks", "6")]);
+}
+
+#[test]
+fn test_shared() {
+    let ctx = Context::default();
+    let mut computed = 0;
+    let a = ctx.shared("squares", || {
+        computed += 1;
+        vec![1, 4, 9]
+    });
+    let b = ctx.shared("squares", || {
+        computed += 1;
+        vec![]
+    });
+
+    assert_eq!(computed, 1);
+    assert_eq!(a, b);
+    assert_eq!(ctx.shared_use().reused, 1);
+
+    // Parts running at the same time still compute the value once.
+    let computed = std::sync::atomic::AtomicUsize::new(0);
+    std::thread::scope(|s| {
+        for _ in 0..4 {
+            s.spawn(|| {
+                ctx.shared("slow", || {
+                    computed.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                    std::thread::sleep(Duration::from_millis(20));
+                })
+            });
+        }
+    });
+    assert_eq!(computed.into_inner(), 1);
+    assert_eq!(ctx.shared_use().reused, 4);
+    assert!(ctx.shared_use().computed >= Duration::from_millis(20));
+}
@@ -1,9 +1,9 @@
-use crate::{Answer, Solution};
+use crate::{error::ParseIn, Answer, Context, Solution, SolveError, SolveResult, Tag};
 
 #[derive(Clone, Copy)]
 pub struct Day01;
 
-fn read_columns(input: &str) -> (Vec<i32>, Vec<i32>) {
+fn read_columns(input: &str) -> Result<(Vec<i32>, Vec<i32>), SolveError> {
     let mut left = Vec::<i32>::new();
     let mut right = Vec::<i32>::new();
     let mut is_left = true;
@@ -14,22 +14,25 @@ fn read_columns(input: &str) -> (Vec<i32>, Vec<i32>) {
             false => &mut right,
         };
 
-        column.push(n.parse().unwrap());
+        column.push(n.parse_in(input)?);
         is_left = !is_left;
     }
 
-    (left, right)
+    Ok((left, right))
 }
 
 impl Solution for Day01 {
     fn day_number(&self) -> i32 {
         1
     }
-    fn clone_dyn(&self) -> Box<dyn Solution> {
-        Box::new(*self)
+    fn title(&self) -> Option<&'static str> {
+        Some("Historian Hysteria")
     }
-    fn part_one(&self, input: &str) -> Answer {
-        let (mut left, mut right) = read_columns(input);
+    fn tags(&self) -> &'static [Tag] {
+        &[Tag::Parsing]
+    }
+    fn part_one(&self, input: &str, _ctx: &Context) -> SolveResult {
+        let (mut left, mut right) = read_columns(input)?;
         left.sort();
         right.sort();
 
@@ -38,11 +41,11 @@ impl Solution for Day01 {
             sum += (l - r).abs();
         }
 
-        return sum.into();
+        Ok(sum.into())
     }
 
-    fn part_two(&self, input: &str) -> Answer {
-        let (left, mut right) = read_columns(input);
+    fn part_two(&self, input: &str, _ctx: &Context) -> SolveResult {
+        let (left, mut right) = read_columns(input)?;
         right.sort();
 
         let mut sum = 0;
@@ -53,6 +56,6 @@ impl Solution for Day01 {
             sum += i * (upper - lower);
         }
 
-        return sum.into();
+        Ok(sum.into())
     }
 }
@@ -1,20 +1,20 @@
-use crate::{Answer, Solution};
+use crate::{error::ParseIn, Answer, Context, Solution, SolveError, SolveResult, Tag};
 
 #[derive(Clone, Copy)]
 pub struct Day02;
 
-fn read_reports(input: &str) -> Vec<Vec<i32>> {
+fn read_reports(input: &str) -> Result<Vec<Vec<i32>>, SolveError> {
     let mut reports = Vec::new();
 
     for line in input.lines() {
         let report = line
             .split_whitespace()
-            .map(|s| s.parse().unwrap())
-            .collect();
+            .map(|s| s.parse_in(input))
+            .collect::<Result<_, _>>()?;
         reports.push(report);
     }
 
-    reports
+    Ok(reports)
 }
 
 fn is_safe(report: &Vec<i32>) -> bool {
@@ -31,18 +31,23 @@ impl Solution for Day02 {
     fn day_number(&self) -> i32 {
         2
     }
-    fn clone_dyn(&self) -> Box<dyn Solution> {
-        Box::new(*self)
+
+    fn title(&self) -> Option<&'static str> {
+        Some("Red-Nosed Reports")
+    }
+
+    fn tags(&self) -> &'static [Tag] {
+        &[Tag::Parsing]
     }
 
-    fn part_one(&self, input: &str) -> Answer {
-        let reports = read_reports(input);
+    fn part_one(&self, input: &str, _ctx: &Context) -> SolveResult {
+        let reports = read_reports(input)?;
         let count = reports.into_iter().filter(is_safe).count();
-        count.into()
+        Ok(count.into())
     }
 
-    fn part_two(&self, input: &str) -> Answer {
-        let reports = read_reports(input);
+    fn part_two(&self, input: &str, _ctx: &Context) -> SolveResult {
+        let reports = read_reports(input)?;
         let count = reports
             .into_iter()
             .filter(|rep| {
@@ -56,6 +61,6 @@ impl Solution for Day02 {
                 false
             })
             .count();
-        count.into()
+        Ok(count.into())
     }
 }
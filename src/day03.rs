@@ -1,6 +1,6 @@
 use regex::Regex;
 
-use crate::{Answer, Solution};
+use crate::{error::ParseIn, Answer, Context, Part, Solution, SolveError, SolveResult, Tag};
 
 #[derive(Clone, Copy)]
 pub struct Day03;
@@ -9,27 +9,57 @@ impl Solution for Day03 {
     fn day_number(&self) -> i32 {
         3
     }
-    fn clone_dyn(&self) -> Box<dyn Solution> {
-        Box::new(*self)
+
+    fn title(&self) -> Option<&'static str> {
+        Some("Mull It Over")
+    }
+
+    fn tags(&self) -> &'static [Tag] {
+        &[Tag::Parsing]
+    }
+
+    fn bonus_parts(&self) -> &'static [&'static str] {
+        &["three"]
+    }
+
+    fn bonus_part(&self, name: &str, input: &str, _ctx: &Context) -> SolveResult {
+        match name {
+            "three" => part_three(input),
+            _ => Err(SolveError::unknown_part(name)),
+        }
     }
 
-    fn part_one(&self, input: &str) -> Answer {
+    fn alt_impls(&self, part: Part) -> &'static [&'static str] {
+        match part {
+            Part::Two => &["scanner"],
+            _ => &[],
+        }
+    }
+
+    fn alt_impl(&self, part: Part, name: &str, input: &str, _ctx: &Context) -> SolveResult {
+        match (part, name) {
+            (Part::Two, "scanner") => Ok(part_two_2(input).into()),
+            _ => Err(SolveError::unknown_impl(part, name)),
+        }
+    }
+
+    fn part_one(&self, input: &str, _ctx: &Context) -> SolveResult {
         let mul = Regex::new(r"mul\((\d+),(\d+)\)").unwrap();
 
         let mut sum = 0;
         for cap in mul.captures_iter(input) {
             let g = cap.extract::<2>().1;
-            let a: i32 = g[0].parse().unwrap();
-            let b: i32 = g[1].parse().unwrap();
+            let a: i32 = g[0].parse_in(input)?;
+            let b: i32 = g[1].parse_in(input)?;
 
             sum += a * b;
         }
 
-        sum.into()
+        Ok(sum.into())
     }
 
     #[allow(unreachable_code)]
-    fn part_two(&self, input: &str) -> Answer {
+    fn part_two(&self, input: &str, _ctx: &Context) -> SolveResult {
         let mul = Regex::new(r"mul\((\d+),(\d+)\)|do\(\)|don't\(\)").unwrap();
 
         let mut sum = 0;
@@ -45,18 +75,15 @@ impl Solution for Day03 {
                     enable = false;
                 }
                 "mul" if enable => {
-                    let a: i32 = cap.get(1).unwrap().as_str().parse().unwrap();
-                    let b: i32 = cap.get(2).unwrap().as_str().parse().unwrap();
+                    let a: i32 = cap.get(1).unwrap().as_str().parse_in(input)?;
+                    let b: i32 = cap.get(2).unwrap().as_str().parse_in(input)?;
                     sum += a * b;
                 }
                 _ => (),
             }
         }
 
-        let sum2 = part_two_2(input);
-        assert_eq!(sum, sum2);
-
-        sum.into()
+        Ok(sum.into())
     }
 }
 
@@ -130,18 +157,16 @@ fn parse_exact(j: &mut usize, chars: &Vec<char>, expect: &str) -> Option<()> {
 ///
 /// For all i,j in `0..muls.len()`, i < j, compute sum of all
 /// `(a_i * b_j + a_j..b_i)`, where `..` means integer concatenation.
-fn part_three(input: &str) -> i64 {
+fn part_three(input: &str) -> SolveResult {
     let pattern = Regex::new(r"mul\((\d+),(\d+)\)").unwrap();
 
-    let muls: Vec<_> = pattern
+    let muls: Vec<(i64, i64)> = pattern
         .captures_iter(input)
         .map(|cap| {
             let g = cap.extract::<2>().1;
-            let a: i64 = g[0].parse().unwrap();
-            let b: i64 = g[1].parse().unwrap();
-            (a, b)
+            Ok((g[0].parse_in(input)?, g[1].parse_in(input)?))
         })
-        .collect();
+        .collect::<Result<_, SolveError>>()?;
 
     let mut sum: i64 = 0;
     for i in 0..muls.len() {
@@ -153,7 +178,7 @@ fn part_three(input: &str) -> i64 {
         }
     }
 
-    sum.into()
+    Ok(sum.into())
 }
 fn cat(a: i64, b: i64) -> i64 {
     let digits = b.to_string().len() as u32;
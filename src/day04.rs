@@ -1,6 +1,6 @@
-use std::ops::{Index, IndexMut};
+use std::{iter, str::FromStr};
 
-use crate::{vec::Vec2i, Answer, Solution};
+use crate::{utils::Grid2D, vec::Vec2i, Answer, Context, Param, Solution, SolveResult, Tag};
 
 #[derive(Clone, Copy)]
 pub struct Day04;
@@ -9,142 +9,83 @@ impl Solution for Day04 {
     fn day_number(&self) -> i32 {
         4
     }
-    fn clone_dyn(&self) -> Box<dyn Solution> {
-        Box::new(*self)
-    }
 
-    fn part_one(&self, input: &str) -> Answer {
-        let board = Board::from_str(input);
-        board.word_count("XMAS").into()
+    fn title(&self) -> Option<&'static str> {
+        Some("Ceres Search")
     }
 
-    fn part_two(&self, input: &str) -> Answer {
-        let board = Board::from_str(input);
-        board.x_shape_mas_count().into()
+    fn tags(&self) -> &'static [Tag] {
+        &[Tag::Grid]
     }
-}
-
-struct Board {
-    width: i32,
-    height: i32,
-    data: Vec<char>,
-}
-impl Board {
-    fn from_str(input: &str) -> Self {
-        let lines = input.lines();
-
-        let width = lines.clone().next().unwrap().len() as i32;
-        let height = lines.clone().filter(|l| l.trim().len() != 0).count() as i32;
-        let mut data = Vec::new();
-        data.reserve((width * height) as _);
 
-        for l in lines {
-            if data.len() as i32 == width * height {
-                break;
-            }
-            assert_eq!(l.len(), width as usize);
-
-            for c in l.chars() {
-                data.push(c);
-            }
-        }
-        Self {
-            width,
-            height,
-            data,
-        }
+    fn params(&self) -> Vec<Param> {
+        vec![Param::new("word", "XMAS".to_owned())]
     }
 
-    fn word_count(&self, word: &str) -> i32 {
-        let mut count = 0;
-
-        for sq in self.squares() {
-            'dirs: for dir in Vec2i::directions_8() {
-                for (i, expect_ch) in word.chars().enumerate() {
-                    let next_ch = match self.get(sq + dir * i as i32) {
-                        None => continue 'dirs,
-                        Some(c) => *c,
-                    };
-
-                    if next_ch != expect_ch {
-                        continue 'dirs;
-                    }
-                }
-
-                // all letters were equal at this point
-                count += 1;
-            }
-        }
-        count
+    fn part_one(&self, input: &str, ctx: &Context) -> SolveResult {
+        let grid = Grid2D::from_str(input).map_err(|e| e.locate(input))?;
+        let word: String = ctx.param("word")?;
+        Ok(word_count(&grid, &word).into())
     }
 
-    fn x_shape_mas_count(&self) -> i32 {
-        let mut count = 0;
-
-        for sq in self.squares() {
-            let x_edge = sq.x == 0 || sq.x == self.width - 1;
-            let y_edge = sq.y == 0 || sq.y == self.height - 1;
-
-            if x_edge || y_edge || self[sq] != 'A' {
-                continue;
-            }
-            for mut dir in Vec2i::directions_4_diag() {
-                if self[sq + dir] != 'M' {
-                    continue;
-                }
-                dir = dir.rot_y2x();
-                if self[sq + dir] != 'M' {
-                    continue;
-                }
-                dir = dir.rot_y2x();
-                if self[sq + dir] != 'S' {
-                    continue;
-                }
-                dir = dir.rot_y2x();
-                if self[sq + dir] != 'S' {
-                    continue;
-                }
-                count += 1;
-            }
-        }
-        count
+    fn part_two(&self, input: &str, _ctx: &Context) -> SolveResult {
+        let grid = Grid2D::from_str(input).map_err(|e| e.locate(input))?;
+        Ok(x_shape_mas_count(&grid).into())
     }
 }
 
-impl Index<Vec2i> for Board {
-    type Output = char;
-    fn index(&self, index: Vec2i) -> &Self::Output {
-        self.get(index).unwrap()
-    }
-}
-impl IndexMut<Vec2i> for Board {
-    fn index_mut(&mut self, index: Vec2i) -> &mut Self::Output {
-        self.get_mut(index).unwrap()
-    }
+/// The grid turned by 0, 90, 180 and 270 degrees.
+fn rotations(grid: &Grid2D<char>) -> impl Iterator<Item = Grid2D<char>> {
+    iter::successors(Some(grid.clone()), |g| Some(g.rotate_cw())).take(4)
 }
 
-impl Board {
-    fn squares(&self) -> impl Iterator<Item = Vec2i> {
-        let w = self.width;
-        (0..self.data.len() as i32)
-            .into_iter()
-            .map(move |i| (i % w, i / w).into())
-    }
+/// Rightwards and down-right in all four rotations covers all 8 directions.
+fn word_count(grid: &Grid2D<char>, word: &str) -> usize {
+    let matches_at = |grid: &Grid2D<char>, pos: Vec2i, dir: Vec2i| {
+        word.chars()
+            .enumerate()
+            .all(|(i, c)| grid.get(pos + dir * i as i32) == Some(&c))
+    };
+
+    rotations(grid)
+        .map(|grid| {
+            let dirs = [Vec2i::new(1, 0), Vec2i::new(1, 1)];
+            grid.iter_vec2()
+                .map(|pos| {
+                    dirs.iter()
+                        .filter(|&&dir| matches_at(&grid, pos, dir))
+                        .count()
+                })
+                .sum::<usize>()
+        })
+        .sum()
+}
 
-    pub fn get(&self, index: Vec2i) -> Option<&char> {
-        let (x, y) = index.into();
-        if x >= 0 && x < self.width && y >= 0 && y < self.height {
-            Some(&self.data[(x + y * self.width) as usize])
-        } else {
-            None
-        }
-    }
-    pub fn get_mut(&mut self, index: Vec2i) -> Option<&mut char> {
-        let (x, y) = index.into();
-        if x >= 0 && x < self.width && y >= 0 && y < self.height {
-            Some(&mut self.data[(x + y * self.width) as usize])
-        } else {
-            None
-        }
-    }
+/// Counts the pattern below, in all four rotations.
+/// ```text
+/// M.M
+/// .A.
+/// S.S
+/// ```
+fn x_shape_mas_count(grid: &Grid2D<char>) -> usize {
+    let pattern = [
+        (-1, -1, 'M'),
+        (1, -1, 'M'),
+        (0, 0, 'A'),
+        (-1, 1, 'S'),
+        (1, 1, 'S'),
+    ];
+    let matches_at = |grid: &Grid2D<char>, pos: Vec2i| {
+        pattern
+            .iter()
+            .all(|&(x, y, c)| grid.get(pos + Vec2i::new(x, y)) == Some(&c))
+    };
+
+    rotations(grid)
+        .map(|grid| {
+            grid.iter_vec2()
+                .filter(|&pos| matches_at(&grid, pos))
+                .count()
+        })
+        .sum()
 }
@@ -9,7 +9,10 @@ use std::{
 
 use multimap::MultiMap;
 
-use crate::{utils::*, vec::Vec2i, Answer, Solution};
+use crate::{
+    error::ParseIn, utils::*, vec::Vec2i, Answer, Context, ParsedSolution, Solution, SolveError,
+    SolveResult, Tag,
+};
 
 #[derive(Clone, Copy)]
 pub struct Day05;
@@ -18,7 +21,7 @@ struct Order {
     before: MultiMap<i32, i32>,
 }
 impl Order {
-    fn from_str(input: &str) -> Self {
+    fn from_str(input: &str) -> Result<Self, SolveError> {
         let mut before = MultiMap::new();
         //let mut after = MultiMap::new();
 
@@ -28,15 +31,16 @@ impl Order {
             }
             let [a, b] = line
                 .split("|")
-                .map(|s| s.parse::<i32>().unwrap())
-                .destruct();
+                .try_destruct()
+                .ok_or_else(|| SolveError::new("expected a rule `a|b`").at(input, line))?;
+            let (a, b): (i32, i32) = (a.parse_in(input)?, b.parse_in(input)?);
 
             //after.insert(a, b);
             before.insert(b, a);
         }
 
         //make_transitive(&mut before);
-        Self { before }
+        Ok(Self { before })
     }
 
     /// Is `a` before `b`
@@ -51,22 +55,44 @@ impl Order {
     }
 }
 
-impl Solution for Day05 {
+pub struct Manual {
+    order: Order,
+    updates: Vec<Vec<i32>>,
+}
+
+impl ParsedSolution for Day05 {
+    type Parsed = Manual;
+
     fn day_number(&self) -> i32 {
         5
     }
-    fn clone_dyn(&self) -> Box<dyn Solution> {
-        Box::new(*self)
+
+    fn title(&self) -> Option<&'static str> {
+        Some("Print Queue")
+    }
+
+    fn tags(&self) -> &'static [Tag] {
+        &[Tag::Graph, Tag::Parsing]
     }
 
-    fn part_one(&self, input: &str) -> Answer {
-        let [order_str, prints] = input.split("\n\n").destruct();
-        let order = Order::from_str(order_str);
+    fn parse(&self, input: &str) -> Result<Manual, SolveError> {
+        let [order_str, prints] = input.split("\n\n").try_destruct().ok_or_else(|| {
+            SolveError::new("expected rules and updates separated by a blank line")
+        })?;
+        let order = Order::from_str(order_str)?;
+        let updates = prints
+            .lines()
+            .map(|list| list.split(",").map(|s| s.parse_in(input)).collect())
+            .collect::<Result<_, _>>()?;
+
+        Ok(Manual { order, updates })
+    }
 
-        let mut sum = 0;
-        'list: for list in prints.lines() {
-            let list: Vec<i32> = list.split(",").map(|s| s.parse().unwrap()).collect();
+    fn part_one(&self, manual: &Manual, _ctx: &Context) -> SolveResult {
+        let order = &manual.order;
 
+        let mut sum = 0;
+        'list: for list in &manual.updates {
             for i in 0..list.len() {
                 for j in i..list.len() {
                     if i != j && order.is_before(list[j], list[i]) {
@@ -78,16 +104,15 @@ impl Solution for Day05 {
             sum += list[list.len() / 2];
         }
 
-        sum.into()
+        Ok(sum.into())
     }
 
-    fn part_two(&self, input: &str) -> Answer {
-        let [order_str, prints] = input.split("\n\n").destruct();
-        let order = Order::from_str(order_str);
+    fn part_two(&self, manual: &Manual, _ctx: &Context) -> SolveResult {
+        let order = &manual.order;
 
         let mut sum = 0;
-        for list_str in prints.lines() {
-            let mut list: Vec<i32> = list_str.split(",").map(|s| s.parse().unwrap()).collect();
+        for list in &manual.updates {
+            let mut list = list.clone();
 
             let mut consider = false;
             let mut ordered = false;
@@ -110,7 +135,7 @@ impl Solution for Day05 {
             }
         }
 
-        sum.into()
+        Ok(sum.into())
     }
 }
 
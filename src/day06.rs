@@ -1,6 +1,11 @@
-use std::collections::HashSet;
+use std::str::FromStr;
 
-use crate::{utils::Grid2D, vec::Vec2i, Answer, Solution};
+use crate::{
+    bitgrid::{BitGrid, DirBitGrid},
+    utils::Grid2D,
+    vec::Vec2i,
+    Answer, Context, Frame, ParsedSolution, Solution, SolveError, SolveResult, Tag,
+};
 
 #[derive(Debug, Clone)]
 pub struct Day06;
@@ -11,69 +16,121 @@ struct Guard {
     dir: Vec2i,
 }
 
-fn get_guard_start(grid: &Grid2D<char>) -> Guard {
-    let pos: Vec2i = grid.index_of(|c| "^v><".contains(*c)).unwrap().into();
+fn get_guard_start(grid: &Grid2D<char>) -> Result<Guard, SolveError> {
+    let pos: Vec2i = grid
+        .index_of(|c| "^v><".contains(*c))
+        .ok_or_else(|| SolveError::new("there is no guard on the map"))?
+        .into();
     let dir: Vec2i = match grid[pos] {
         '^' => (0, -1).into(),
         'v' => (0, 1).into(),
         '<' => (-1, 0).into(),
         '>' => (1, 0).into(),
-        _ => panic!(),
+        _ => unreachable!(),
     };
-    Guard { pos, dir }
+    Ok(Guard { pos, dir })
 }
 
-fn walk_guard(mut guard: Guard, grid: &Grid2D<char>) -> (HashSet<Vec2i>, bool) {
-    let mut circular = false;
-    let mut visited = HashSet::new();
+/// Walks the guard until it leaves the map or runs in circles, the latter
+/// is returned. `visited` is cleared first so one allocation can serve many
+/// walks. Pushes a frame to `frames` whenever the guard turns.
+fn walk_guard(
+    mut guard: Guard,
+    grid: &Grid2D<char>,
+    visited: &mut DirBitGrid,
+    frames: Option<&Context>,
+) -> bool {
+    visited.clear();
     while let Some(_) = grid.get(guard.pos) {
         let in_front = guard.pos + guard.dir;
-        if visited.insert((guard.pos, guard.dir)) == false {
-            circular = true;
-            break;
+        if !visited.insert(guard.pos, guard.dir) {
+            return true;
         }
 
         match grid.get(in_front) {
-            Some('#') => guard.dir = guard.dir.rot_x2y(),
+            Some('#') => {
+                guard.dir = guard.dir.rot_x2y();
+                if let Some(ctx) = frames {
+                    ctx.frame(|| draw_walk(grid, visited, guard));
+                }
+            }
             _ => guard.pos = in_front,
         }
     }
+    false
+}
+
+/// Cells the guard passes on its way off the map.
+fn guard_path(guard: Guard, grid: &Grid2D<char>, ctx: &Context) -> BitGrid {
+    let mut visited = DirBitGrid::new(grid.width(), grid.height());
+    walk_guard(guard, grid, &mut visited, Some(ctx));
+    visited.positions()
+}
 
-    (visited.iter().map(|(pos, _)| *pos).collect(), circular)
+fn draw_walk(grid: &Grid2D<char>, visited: &DirBitGrid, guard: Guard) -> Frame {
+    let mut grid = grid.clone();
+    for c in grid.iter_mut().filter(|c| "^v<>".contains(**c)) {
+        *c = '.';
+    }
+    let mut steps = 0;
+    for (pos, _) in visited.iter() {
+        grid[pos] = 'X';
+        steps += 1;
+    }
+    grid[guard.pos] = match (guard.dir.x, guard.dir.y) {
+        (0, -1) => '^',
+        (0, 1) => 'v',
+        (-1, 0) => '<',
+        _ => '>',
+    };
+    Frame::new(grid).note(format!("{steps} steps"))
 }
 
-impl Solution for Day06 {
+impl ParsedSolution for Day06 {
+    type Parsed = Grid2D<char>;
+
     fn day_number(&self) -> i32 {
         6
     }
-    fn clone_dyn(&self) -> Box<dyn Solution> {
-        Box::new(self.clone())
+
+    fn title(&self) -> Option<&'static str> {
+        Some("Guard Gallivant")
+    }
+
+    fn tags(&self) -> &'static [Tag] {
+        &[Tag::Grid, Tag::Simulation]
+    }
+
+    fn parse(&self, input: &str) -> Result<Grid2D<char>, SolveError> {
+        Grid2D::from_str(input).map_err(|e| e.locate(input))
     }
 
-    fn part_one(&self, input: &str) -> Answer {
-        let grid: Grid2D<char> = input.parse().unwrap();
-        let guard = get_guard_start(&grid);
-        let (visited, _) = walk_guard(guard, &grid);
-        visited.len().into()
+    fn part_one(&self, grid: &Grid2D<char>, ctx: &Context) -> SolveResult {
+        let guard = get_guard_start(grid)?;
+        let visited = ctx.shared("visited", || guard_path(guard, grid, ctx));
+        Ok(visited.len().into())
     }
 
-    fn part_two(&self, input: &str) -> Answer {
-        let mut grid: Grid2D<char> = input.parse().unwrap();
-        let guard = get_guard_start(&grid);
-        let (visited, _) = walk_guard(guard, &grid);
+    fn part_two(&self, grid: &Grid2D<char>, ctx: &Context) -> SolveResult {
+        let guard = get_guard_start(grid)?;
+        let visited = ctx.shared("visited", || guard_path(guard, grid, ctx));
+        let mut walk = DirBitGrid::new(grid.width(), grid.height());
+        let mut grid = grid.clone();
         let mut sum = 0;
 
-        for v in visited {
+        let progress = ctx.progress();
+        progress.set_total(visited.len() as u64);
+        for v in visited.iter() {
+            progress.inc();
             let old = grid[v];
 
             grid[v] = '#';
-            let (_, circular) = walk_guard(guard, &grid);
-            if circular {
+            if walk_guard(guard, &grid, &mut walk, None) {
                 sum += 1;
             }
             grid[v] = old;
         }
 
-        sum.into()
+        Ok(sum.into())
     }
 }
@@ -1,77 +1,119 @@
-use std::{str::FromStr, thread};
+use std::{fmt, str::FromStr, thread};
 
-use crate::{utils::DestructIterator, Answer, Solution};
+use crate::{
+    error::ParseIn, utils::DestructIterator, Answer, Context, Param, Solution, SolveError,
+    SolveResult, Tag,
+};
 
 #[derive(Clone, Copy)]
 pub struct Day07;
 
+#[derive(Clone, Copy)]
 enum Operator {
     Add,
     Mul,
     Cat,
 }
 
+/// Operators that may be placed between the operands, written as their
+/// symbols: `+`, `*` and `|` for concatenation.
+struct Operators(Vec<Operator>);
+
+impl FromStr for Operators {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let ops = s
+            .chars()
+            .map(|c| match c {
+                '+' => Ok(Operator::Add),
+                '*' => Ok(Operator::Mul),
+                '|' => Ok(Operator::Cat),
+                _ => Err(format!("unknown operator {c:?}")),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        match ops.is_empty() {
+            true => Err("no operators given".to_owned()),
+            false => Ok(Self(ops)),
+        }
+    }
+}
+impl fmt::Display for Operators {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for op in &self.0 {
+            let c = match op {
+                Operator::Add => '+',
+                Operator::Mul => '*',
+                Operator::Cat => '|',
+            };
+            fmt::Write::write_char(f, c)?;
+        }
+        Ok(())
+    }
+}
 
 impl Solution for Day07 {
     fn day_number(&self) -> i32 {
         7
     }
 
-    fn clone_dyn(&self) -> Box<dyn Solution> {
-        Box::new(*self)
+    fn title(&self) -> Option<&'static str> {
+        Some("Bridge Repair")
     }
 
-    fn part_one(&self, input: &str) -> Answer {
-        compute_result_sum(input, |op_cnt| 2i64.pow(op_cnt as u32), to_bin_ops)
+    fn tags(&self) -> &'static [Tag] {
+        &[Tag::Parsing, Tag::Dp]
     }
 
-    fn part_two(&self, input: &str) -> Answer {
-        compute_result_sum(input, |op_cnt| 3i64.pow(op_cnt as u32), to_tern_ops)
+    fn params(&self) -> Vec<Param> {
+        vec![
+            Param::new("ops_one", Operators(vec![Operator::Add, Operator::Mul])),
+            Param::new(
+                "ops_two",
+                Operators(vec![Operator::Add, Operator::Mul, Operator::Cat]),
+            ),
+        ]
     }
-}
 
-fn to_bin_ops(mut i: i64, amt: i64) -> impl Iterator<Item = Operator> {
-    (0..amt).into_iter().map(move |_| {
-        let ii = i;
-        i /= 2;
-        match ii % 2 {
-            0 => Operator::Add,
-            1 => Operator::Mul,
-            _ => panic!(),
-        }
-    })
+    fn part_one(&self, input: &str, ctx: &Context) -> SolveResult {
+        let ops: Operators = ctx.param("ops_one")?;
+        compute_result_sum(input, &ops.0, ctx)
+    }
+
+    fn part_two(&self, input: &str, ctx: &Context) -> SolveResult {
+        let ops: Operators = ctx.param("ops_two")?;
+        compute_result_sum(input, &ops.0, ctx)
+    }
 }
 
-fn to_tern_ops(mut i: i64, amt: i64) -> impl Iterator<Item = Operator> {
-    (0..amt).into_iter().map(move |_| {
-        let ii = i;
-        i /= 3;
-        match ii % 3 {
-            0 => Operator::Add,
-            1 => Operator::Mul,
-            2 => Operator::Cat,
-            _ => panic!(),
-        }
+fn to_ops(mut i: i64, amt: i64, ops: &[Operator]) -> impl Iterator<Item = Operator> + '_ {
+    let base = ops.len() as i64;
+    (0..amt).map(move |_| {
+        let op = ops[(i % base) as usize];
+        i /= base;
+        op
     })
 }
-fn compute_result_sum<I>(
-    input: &str,
-    option_count: fn(i64) -> i64,
-    operator_iter: fn(i64, i64) -> I,
-) -> Answer
-where
-    I: Iterator<Item = Operator>,
-{
+
+fn compute_result_sum(input: &str, ops: &[Operator], ctx: &Context) -> SolveResult {
     let mut sum: i64 = 0;
 
+    let progress = ctx.progress();
+    progress.set_total(input.lines().count() as u64);
     for line in input.lines() {
-        let [result_str, ops_str] = line.split(':').destruct();
+        progress.inc();
+        let [result_str, ops_str] = line
+            .split(':')
+            .try_destruct()
+            .ok_or_else(|| SolveError::new("expected `result: operands`").at(input, line))?;
 
-        let expected: i64 = result_str.parse().unwrap();
+        let expected: i64 = result_str.parse_in(input)?;
         let operands: Vec<i64> = ops_str
             .split_whitespace()
-            .map(|s| s.parse().unwrap())
-            .collect();
+            .map(|s| s.parse_in(input))
+            .collect::<Result<_, _>>()?;
+        if operands.is_empty() {
+            return Err(SolveError::new("expected at least one operand").at(input, ops_str));
+        }
 
         let operator_order: Vec<i64> = ops_str
             .split_whitespace()
@@ -80,10 +122,10 @@ where
 
         let operator_cnt = operands.len() as i64 - 1;
 
-        for i in 0..option_count(operator_cnt) {
+        for i in 0..(ops.len() as i64).pow(operator_cnt as u32) {
             let mut result = operands[0];
 
-            let bits = operator_iter(i, operator_cnt);
+            let bits = to_ops(i, operator_cnt, ops);
             for ((opi, x), op) in operands.iter().enumerate().skip(1).zip(bits) {
                 result = match op {
                     Operator::Add => result + x,
@@ -99,5 +141,5 @@ where
         }
     }
 
-    sum.into()
+    Ok(sum.into())
 }
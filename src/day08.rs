@@ -1,7 +1,7 @@
 use multimap::MultiMap;
 
 use crate::utils::*;
-use crate::{vec::Vec2i, Answer, Solution};
+use crate::{vec::Vec2i, Answer, Context, Solution, SolveError, SolveResult, Tag};
 use std::{
     collections::{HashMap, HashSet},
     str::FromStr,
@@ -31,12 +31,16 @@ impl Solution for Day08 {
         8
     }
 
-    fn clone_dyn(&self) -> Box<dyn Solution> {
-        Box::new(*self)
+    fn title(&self) -> Option<&'static str> {
+        Some("Resonant Collinearity")
     }
 
-    fn part_one(&self, input: &str) -> Answer {
-        let grid: Grid2D<_> = input.parse().unwrap();
+    fn tags(&self) -> &'static [Tag] {
+        &[Tag::Grid]
+    }
+
+    fn part_one(&self, input: &str, _ctx: &Context) -> SolveResult {
+        let grid = Grid2D::<char>::from_str(input).map_err(|e| e.locate(input))?;
         let antennas = Antennas::from(&grid);
 
         let mut antinodes = HashSet::new();
@@ -52,11 +56,11 @@ impl Solution for Day08 {
                 }
             }
         }
-        antinodes.len().into()
+        Ok(antinodes.len().into())
     }
 
-    fn part_two(&self, input: &str) -> Answer {
-        let grid: Grid2D<_> = input.parse().unwrap();
+    fn part_two(&self, input: &str, _ctx: &Context) -> SolveResult {
+        let grid = Grid2D::<char>::from_str(input).map_err(|e| e.locate(input))?;
         let antennas = Antennas::from(&grid);
 
         let mut antinodes = HashSet::new();
@@ -73,6 +77,6 @@ impl Solution for Day08 {
                 }
             }
         }
-        antinodes.len().into()
+        Ok(antinodes.len().into())
     }
 }
@@ -1,4 +1,4 @@
-use crate::{Answer, Solution};
+use crate::{utils::Grid2D, Answer, Context, Frame, Solution, SolveError, SolveResult, Tag};
 use std::{collections::BTreeSet, fmt};
 
 #[derive(Clone, Copy)]
@@ -51,13 +51,17 @@ impl fmt::Debug for Gap {
 }
 
 impl Disk {
-    fn from_disk_map(input: &str) -> Self {
+    fn from_disk_map(input: &str) -> Result<Self, SolveError> {
         let mut blocks = Vec::new();
 
-        for (i, c) in input.chars().enumerate() {
+        let disk_map = input.trim_end();
+        for (i, (offset, c)) in disk_map.char_indices().enumerate() {
             let is_file = i % 2 == 0;
             let file_id = i as u32 / 2;
-            let len = c.to_digit(10).unwrap();
+            let len = c.to_digit(10).ok_or_else(|| {
+                let span = &disk_map[offset..offset + c.len_utf8()];
+                SolveError::new(format!("expected a digit, found {c:?}")).at(input, span)
+            })?;
 
             let val = match is_file {
                 true => Some(file_id),
@@ -69,7 +73,7 @@ impl Disk {
             }
         }
 
-        Self { blocks }
+        Ok(Self { blocks })
     }
 
     fn checksum(&self) -> u64 {
@@ -82,11 +86,15 @@ impl Disk {
         checksum
     }
 
-    fn compact(&mut self) {
+    fn compact(&mut self, ctx: &Context) {
+        let stride = (self.blocks.len() / Self::FRAMES).max(1);
         for i in 0.. {
             if i >= self.blocks.len() {
                 break;
             }
+            if i % stride == 0 {
+                ctx.frame(|| self.draw().note(format!("block {i}")));
+            }
             if self.blocks[i].is_none() {
                 let moved_id = loop {
                     let last = self.blocks.pop().unwrap();
@@ -97,12 +105,17 @@ impl Disk {
                 self.blocks[i] = Some(moved_id);
             }
         }
+        ctx.frame(|| self.draw().note("compacted"));
     }
 
-    fn compact2(&mut self) {
+    fn compact2(&mut self, ctx: &Context) {
         let file_ids: BTreeSet<_> = self.blocks.iter().filter_map(|x| *x).collect();
 
-        for id in file_ids.iter().rev() {
+        let stride = (file_ids.len() / Self::FRAMES).max(1);
+        for (n, id) in file_ids.iter().rev().enumerate() {
+            if n % stride == 0 {
+                ctx.frame(|| self.draw().note(format!("moving file {id}")));
+            }
             let file = self.find_file(*id);
             let Some(gap) = self.first_fit(file.len) else {
                 continue;
@@ -116,6 +129,24 @@ impl Disk {
                 self.blocks[gap.start + i] = Some(*id);
             }
         }
+        ctx.frame(|| self.draw().note("compacted"));
+    }
+
+    /// Roughly how many frames compacting pushes.
+    const FRAMES: usize = 100;
+
+    /// The blocks in rows of 100, files by their id modulo 36, free space as `.`.
+    fn draw(&self) -> Frame {
+        let width = self.blocks.len().clamp(1, 100);
+        let height = self.blocks.len().div_ceil(width);
+        let mut grid = Grid2D::new(width as i32, height as i32, ' ');
+        for (i, block) in self.blocks.iter().enumerate() {
+            grid[((i % width) as i32, (i / width) as i32)] = match block {
+                Some(id) => char::from_digit(id % 36, 36).unwrap(),
+                None => '.',
+            };
+        }
+        Frame::new(grid)
     }
 
     fn find_file(&self, id: u32) -> File {
@@ -168,26 +199,30 @@ impl Solution for Day09 {
         9
     }
 
-    fn clone_dyn(&self) -> Box<dyn Solution> {
-        Box::new(*self)
+    fn title(&self) -> Option<&'static str> {
+        Some("Disk Fragmenter")
+    }
+
+    fn tags(&self) -> &'static [Tag] {
+        &[Tag::Simulation]
     }
 
-    fn part_one(&self, input: &str) -> Answer {
-        let mut disk = Disk::from_disk_map(input);
-        disk.compact();
+    fn part_one(&self, input: &str, ctx: &Context) -> SolveResult {
+        let mut disk = Disk::from_disk_map(input)?;
+        disk.compact(ctx);
         let checksum = disk.checksum();
 
         //println!("{}", &disk);
-        checksum.into()
+        Ok(checksum.into())
     }
 
-    fn part_two(&self, input: &str) -> Answer {
-        let mut disk = Disk::from_disk_map(input);
+    fn part_two(&self, input: &str, ctx: &Context) -> SolveResult {
+        let mut disk = Disk::from_disk_map(input)?;
         //println!("Before\n{}", &disk);
-        disk.compact2();
+        disk.compact2(ctx);
         let checksum = disk.checksum();
 
         //println!("After\n{}", &disk);
-        checksum.into()
+        Ok(checksum.into())
     }
 }
@@ -1,61 +1,54 @@
-use std::{
-    collections::{HashSet, VecDeque},
-    str::FromStr,
-};
+use std::collections::VecDeque;
 
-use crate::{utils::Grid2D, vec::Vec2i, Answer, Solution};
+use crate::{
+    grid::Grid, search::Search, utils::Grid2D, vec::Vec2i, Answer, Context, ParsedSolution,
+    Solution, SolveError, SolveResult, Tag,
+};
 
 #[derive(Clone, Copy)]
 pub struct Day10;
 
 fn count_reachable_peaks(grid: &Grid2D<i32>, start: Vec2i) -> i64 {
-    let mut seen = HashSet::<Vec2i>::new();
-    let mut queue = VecDeque::<Vec2i>::new();
-
-    queue.push_back(start);
-    while let Some(pos) = queue.pop_front() {
-        let h = grid[pos];
-        for dir in Vec2i::directions_4() {
-            let next = pos + dir;
-            let Some(&next_h) = grid.get(next) else {
-                continue;
-            };
-
-            if h + 1 == next_h && seen.insert(next) {
-                queue.push_back(next);
-            }
-        }
-    }
-    seen.into_iter().filter(|x| grid[*x] == 9).count() as _
+    let paths = Search::new(grid)
+        .start(start)
+        .bfs(|_, &h, _, &next_h| h + 1 == next_h);
+    paths.distances().keys().filter(|&&p| grid[p] == 9).count() as _
 }
 
-impl Solution for Day10 {
+impl ParsedSolution for Day10 {
+    type Parsed = Grid2D<i32>;
+
     fn day_number(&self) -> i32 {
         10
     }
-    fn clone_dyn(&self) -> Box<dyn Solution> {
-        Box::new(*self)
+
+    fn title(&self) -> Option<&'static str> {
+        Some("Hoof It")
+    }
+
+    fn tags(&self) -> &'static [Tag] {
+        &[Tag::Grid, Tag::Graph]
+    }
+
+    fn parse(&self, input: &str) -> Result<Grid2D<i32>, SolveError> {
+        Grid2D::parse_with(input, |c| {
+            c.to_digit(10).map(|d| d as i32).ok_or("expected a digit")
+        })
+        .map_err(|e| e.locate(input))
     }
 
-    fn part_one(&self, input: &str) -> Answer {
+    fn part_one(&self, grid: &Grid2D<i32>, _ctx: &Context) -> SolveResult {
         let mut sum = 0;
-        let grid = Grid2D::from_str(input)
-            .unwrap()
-            .map(|c| c.to_digit(10).unwrap() as i32);
 
         for head in grid.iter_indices() {
             if grid[head] == 0 {
-                sum += count_reachable_peaks(&grid, head.into());
+                sum += count_reachable_peaks(grid, head.into());
             }
         }
-        sum.into()
+        Ok(sum.into())
     }
 
-    fn part_two(&self, input: &str) -> Answer {
-        let grid = Grid2D::from_str(input)
-            .unwrap()
-            .map(|c| c.to_digit(10).unwrap() as i32);
-
+    fn part_two(&self, grid: &Grid2D<i32>, _ctx: &Context) -> SolveResult {
         let mut rating = grid.clone().map(|_| 0);
         let mut queue: VecDeque<Vec2i> = grid
             .iter_indices()
@@ -67,29 +60,17 @@ impl Solution for Day10 {
             rating[*pos] = 1;
         }
 
-        println!("grid:\n{}\nrating:\n{}", &grid, &rating);
-
         while let Some(pos) = queue.pop_front() {
             let h = grid[pos];
             //println!("pos = {pos}");
 
-            for dir in Vec2i::directions_4() {
-                let next = pos + dir;
-                let Some(&next_h) = grid.get(next) else {
-                    continue;
-                };
-
+            for (next, &next_h) in grid.neighbors4(pos) {
                 if h + 1 == next_h {
-                    let mut r = 0;
-                    for dir2 in Vec2i::directions_4() {
-                        let prev = next + dir2;
-                        let Some(&prev_h) = grid.get(prev) else {
-                            continue;
-                        };
-                        if prev_h == h {
-                            r += rating[prev];
-                        }
-                    }
+                    let r = grid
+                        .neighbors4(next)
+                        .filter(|&(_, &prev_h)| prev_h == h)
+                        .map(|(prev, _)| rating[prev])
+                        .sum();
 
                     if r > rating[next] {
                         rating[next] = r;
@@ -100,10 +81,11 @@ impl Solution for Day10 {
             //println!("rating:\n{}", &rating);
         }
 
-        grid.iter_indices()
+        Ok(grid
+            .iter_indices()
             .filter(|&p| grid[p] == 9)
             .map(|p| rating[p] as i64)
             .sum::<i64>()
-            .into()
+            .into())
     }
 }
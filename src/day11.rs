@@ -4,7 +4,7 @@ use std::{
     ops::Add,
 };
 
-use crate::Solution;
+use crate::{error::ParseIn, Context, Param, Solution, SolveError, SolveResult, Tag};
 
 #[derive(Clone, Copy)]
 pub struct Day11;
@@ -14,18 +14,30 @@ impl Solution for Day11 {
         11
     }
 
-    fn clone_dyn(&self) -> Box<dyn Solution> {
-        Box::new(*self)
+    fn title(&self) -> Option<&'static str> {
+        Some("Plutonian Pebbles")
     }
 
-    fn part_one(&self, input: &str) -> crate::Answer {
+    fn tags(&self) -> &'static [Tag] {
+        &[Tag::Simulation, Tag::Dp]
+    }
+
+    fn params(&self) -> Vec<Param> {
+        vec![Param::new("blinks_one", 25), Param::new("blinks_two", 75)]
+    }
+
+    fn part_one(&self, input: &str, ctx: &Context) -> SolveResult {
+        let blinks: u32 = ctx.param("blinks_one")?;
         let mut stones: Vec<i64> = input
             .split_whitespace()
-            .map(|s| s.parse().unwrap())
-            .collect();
+            .map(|s| s.parse_in(input))
+            .collect::<Result<_, _>>()?;
 
-        for i in 0..25 {
-            println!("blink {i}, {} stones", stones.len());
+        let progress = ctx.progress();
+        progress.set_total(blinks as u64);
+        for i in 0..blinks {
+            progress.set_done(i as u64);
+            progress.set_message(format!("{} stones", stones.len()));
             let mut new_stones = Vec::new();
 
             for k in &stones {
@@ -54,19 +66,23 @@ impl Solution for Day11 {
         }
 
         let unique: HashSet<_> = stones.iter().collect();
-        println!("{} unique stones", unique.len());
+        progress.set_message(format!("{} stones, {} unique", stones.len(), unique.len()));
 
-        stones.len().into()
+        Ok(stones.len().into())
     }
 
-    fn part_two(&self, input: &str) -> crate::Answer {
+    fn part_two(&self, input: &str, ctx: &Context) -> SolveResult {
+        let blinks: u32 = ctx.param("blinks_two")?;
         let mut stones: HashMap<i64, i64> = input
             .split_whitespace()
-            .map(|s| (s.parse().unwrap(), 1))
-            .collect();
+            .map(|s| Ok((s.parse_in(input)?, 1)))
+            .collect::<Result<_, SolveError>>()?;
 
-        for i in 0..75 {
-            println!("blink {}, {} stones", i + 1, stones.values().sum::<i64>());
+        let progress = ctx.progress();
+        progress.set_total(blinks as u64);
+        for i in 0..blinks {
+            progress.set_done(i as u64);
+            progress.set_message(format!("{} stones", stones.values().sum::<i64>()));
             let mut new_stones = HashMap::new();
 
             for (k, amount) in &stones {
@@ -93,7 +109,7 @@ impl Solution for Day11 {
             //println!("{:?}", &stones);
         }
 
-        stones.values().sum::<i64>().into()
+        Ok(stones.values().sum::<i64>().into())
     }
 }
 
@@ -110,3 +126,12 @@ impl<K: Eq + Hash, V: Clone + Add<V, Output = V>> HashMapAdd<K, V> for HashMap<K
         }
     }
 }
+
+#[test]
+fn test_example() {
+    let ctx = Context::new(Day11.params()).with_param("blinks_one", 6);
+    assert_eq!(Day11.part_one("125 17", &ctx).unwrap().to_string(), "22");
+
+    let ctx = Context::new(Day11.params()).with_param("blinks_two", 25);
+    assert_eq!(Day11.part_two("125 17", &ctx).unwrap().to_string(), "55312");
+}
@@ -1,123 +1,70 @@
-use std::{
-    collections::{HashMap, HashSet, VecDeque},
-    hash::Hash,
-    ops::Add,
-    str::FromStr,
-};
+use std::str::FromStr;
 
-use crate::{utils::Grid2D, vec::Vec2i, Solution};
+use crate::{
+    regions::{Connectivity, Region, Regions},
+    utils::Grid2D,
+    vec::Vec2i,
+    Context, Frame, ParsedSolution, Solution, SolveError, SolveResult, Tag,
+};
 
 #[derive(Clone, Copy)]
 pub struct Day12;
 
-impl Solution for Day12 {
+impl ParsedSolution for Day12 {
+    type Parsed = Grid2D<char>;
+
     fn day_number(&self) -> i32 {
         12
     }
 
-    fn clone_dyn(&self) -> Box<dyn Solution> {
-        Box::new(*self)
+    fn title(&self) -> Option<&'static str> {
+        Some("Garden Groups")
     }
 
-    fn part_one(&self, input: &str) -> crate::Answer {
-        let grid = Grid2D::from_str(input).unwrap();
-        let mut sum = 0;
-
-        let mut used = HashSet::<Vec2i>::new();
-        for pos in grid.iter_vec2() {
-            if used.contains(&pos) {
-                continue;
-            }
-
-            let plot = find_plot_edges(&grid, pos, &mut used);
-            sum += plot.area * plot.perimeter;
-        }
-
-        sum.into()
+    fn tags(&self) -> &'static [Tag] {
+        &[Tag::Grid, Tag::Graph]
     }
 
-    fn part_two(&self, input: &str) -> crate::Answer {
-        let grid = Grid2D::from_str(input).unwrap();
-        let mut sum = 0;
-
-        let mut used = HashSet::<Vec2i>::new();
-        for pos in grid.iter_vec2() {
-            if used.contains(&pos) {
-                continue;
-            }
-
-            let plot = find_plot_corners(&grid, pos, &mut used);
-            sum += plot.area * plot.perimeter;
-        }
-
-        sum.into()
+    fn parse(&self, input: &str) -> Result<Grid2D<char>, SolveError> {
+        Grid2D::from_str(input).map_err(|e| e.locate(input))
     }
-}
 
-struct Plot {
-    area: i32,
-    perimeter: i32,
-}
-fn find_plot_edges(grid: &Grid2D<char>, start: Vec2i, used: &mut HashSet<Vec2i>) -> Plot {
-    let mut perimeter = 0;
-    let mut area = 0;
-    let ch = grid[start];
-
-    let mut queue = VecDeque::from([start]);
-    while let Some(pos) = queue.pop_front() {
-        if used.contains(&pos) {
-            continue;
-        }
-
-        area += 1;
-        used.insert(pos);
-
-        for dir in Vec2i::directions_4() {
-            if grid.get(pos + dir) != Some(&ch) {
-                perimeter += 1;
-            } else {
-                queue.push_back(pos + dir);
-            }
-        }
+    fn part_one(&self, grid: &Grid2D<char>, ctx: &Context) -> SolveResult {
+        let plots = ctx.shared("plots", || find_plots(grid, ctx));
+        let sum: usize = plots.regions.iter().map(|p| p.area() * p.perimeter).sum();
+        Ok(sum.into())
     }
 
-    Plot { area, perimeter }
+    fn part_two(&self, grid: &Grid2D<char>, ctx: &Context) -> SolveResult {
+        let plots = ctx.shared("plots", || find_plots(grid, ctx));
+        let sum: usize = plots.regions.iter().map(|p| p.area() * p.sides).sum();
+        Ok(sum.into())
+    }
 }
 
-fn find_plot_corners(grid: &Grid2D<char>, start: Vec2i, used: &mut HashSet<Vec2i>) -> Plot {
-    let mut corners = 0;
-    let mut area = 0;
-    let ch = grid[start];
-
-    let mut queue = VecDeque::from([start]);
-    while let Some(pos) = queue.pop_front() {
-        if used.contains(&pos) {
-            continue;
-        }
-
-        area += 1;
-        used.insert(pos);
-
-        for dir in Vec2i::directions_4() {
-            let dir_r = dir.rot_x2y();
-            let same_char = |d: Vec2i| grid.get(pos + d) == Some(&ch);
-
-            if !same_char(dir) {
-                if !same_char(dir_r) {
-                    corners += 1;
-                }
-            } else {
-                queue.push_back(pos + dir);
-
-                if same_char(dir_r) && !same_char(dir + dir_r) {
-                    corners += 1;
-                }
-            }
-        }
+/// Pushes a frame showing the plots found so far after each one.
+fn find_plots(grid: &Grid2D<char>, ctx: &Context) -> Regions {
+    let plots = Regions::label(grid, Connectivity::Four);
+    for (label, plot) in plots.regions.iter().enumerate() {
+        ctx.frame(|| draw_plots(grid, &plots, label, plot));
     }
+    plots
+}
 
-    Plot {
-        area,
-        perimeter: corners,
+fn draw_plots(grid: &Grid2D<char>, plots: &Regions, last: usize, plot: &Region) -> Frame {
+    let mut shown = Grid2D::new(grid.width(), grid.height(), '.');
+    for pos in grid
+        .iter_vec2()
+        .filter(|&p| plots.label_at(p).is_some_and(|l| l <= last))
+    {
+        shown[pos] = grid[pos];
     }
+    Frame::new(shown).note(format!(
+        "plot {:?} at {}: area {}, perimeter {}, sides {}",
+        grid[plot.start],
+        plot.start,
+        plot.area(),
+        plot.perimeter,
+        plot.sides
+    ))
 }
@@ -0,0 +1,141 @@
+use std::{
+    fmt::{Display, Formatter},
+    num::ParseIntError,
+    str::FromStr,
+};
+
+use crate::{Answer, Part};
+
+pub type SolveResult = Result<Answer, SolveError>;
+
+/// An error caused by the puzzle input, optionally pointing to the place in
+/// the input where it happened.
+#[derive(Debug)]
+pub struct SolveError {
+    message: String,
+    location: Option<Location>,
+}
+
+/// Position in the input, `line` and `column` are 1-based.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+}
+
+impl SolveError {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            location: None,
+        }
+    }
+
+    /// For [`crate::Solution::bonus_part`] called with a name it does not know.
+    pub fn unknown_part(name: &str) -> Self {
+        Self::new(format!("there is no part `{name}`"))
+    }
+
+    /// For [`crate::Solution::alt_impl`] called with a name it does not know.
+    pub fn unknown_impl(part: Part, name: &str) -> Self {
+        Self::new(format!("part {part} has no implementation `{name}`"))
+    }
+
+    /// Locates the error at `span`, which has to be a slice of `input`.
+    /// Spans from elsewhere leave the error without a location.
+    pub fn at(mut self, input: &str, span: &str) -> Self {
+        let start = input.as_ptr() as usize;
+        let offset = (span.as_ptr() as usize).wrapping_sub(start);
+        if offset <= input.len() && input.is_char_boundary(offset) {
+            let before = &input[..offset];
+            let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+            let line = before.matches('\n').count() + 1;
+            let column = before[line_start..].chars().count() + 1;
+            self.location = Some(Location {
+                line,
+                column,
+                text: line_text(input, line),
+            });
+        }
+        self
+    }
+
+    /// Locates the error at the given 1-based line and column of `input`.
+    pub fn at_line(mut self, input: &str, line: usize, column: usize) -> Self {
+        self.location = Some(Location {
+            line,
+            column,
+            text: line_text(input, line),
+        });
+        self
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn location(&self) -> Option<&Location> {
+        self.location.as_ref()
+    }
+}
+
+fn line_text(input: &str, line: usize) -> String {
+    let text = input.lines().nth(line - 1).unwrap_or_default();
+    text.trim_end_matches('\r').to_owned()
+}
+
+impl Display for SolveError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.location {
+            Some(loc) => write!(
+                f,
+                "line {}, column {}: {}",
+                loc.line, loc.column, self.message
+            ),
+            None => f.write_str(&self.message),
+        }
+    }
+}
+
+impl std::error::Error for SolveError {}
+
+impl From<ParseIntError> for SolveError {
+    fn from(value: ParseIntError) -> Self {
+        Self::new(value.to_string())
+    }
+}
+
+pub trait ParseIn {
+    /// Like [`str::parse`], but on failure the error points to where `self`
+    /// lies inside `input`.
+    fn parse_in<T>(&self, input: &str) -> Result<T, SolveError>
+    where
+        T: FromStr,
+        T::Err: Display;
+}
+impl ParseIn for str {
+    fn parse_in<T>(&self, input: &str) -> Result<T, SolveError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.parse()
+            .map_err(|e: T::Err| SolveError::new(format!("{e}: {self:?}")).at(input, self))
+    }
+}
+
+#[test]
+fn test_error_location() {
+    let input = "12 34\n56 7x\n";
+    let token = input.split_whitespace().nth(3).unwrap();
+    let err = token.parse_in::<i32>(input).unwrap_err();
+
+    let loc = err.location().unwrap();
+    assert_eq!((loc.line, loc.column), (2, 4));
+    assert_eq!(loc.text, "56 7x");
+
+    let elsewhere = String::from("7x");
+    let err = elsewhere.parse_in::<i32>(input).unwrap_err();
+    assert!(err.location().is_none());
+}
@@ -0,0 +1,83 @@
+use std::{
+    fmt::{Display, Formatter},
+    fs,
+    io::{self, Write},
+    path::Path,
+    thread,
+    time::Duration,
+};
+
+use crate::utils::Grid2D;
+
+/// Snapshot of a solution's state, pushed through [`crate::Context::frame`]
+/// to watch how it progresses.
+#[derive(Clone)]
+pub struct Frame {
+    pub grid: Grid2D<char>,
+    pub notes: Vec<String>,
+}
+
+impl Frame {
+    pub fn new(grid: Grid2D<char>) -> Self {
+        Self {
+            grid,
+            notes: Vec::new(),
+        }
+    }
+
+    /// Adds a line of text shown below the grid.
+    pub fn note(mut self, note: impl Into<String>) -> Self {
+        self.notes.push(note.into());
+        self
+    }
+}
+
+impl Display for Frame {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for row in self.grid.rows() {
+            writeln!(f, "{}", row.iter().collect::<String>())?;
+        }
+        for note in &self.notes {
+            writeln!(f, "{note}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Writes every frame to its own file `{dir}/{prefix}-00000.txt`, ...
+pub fn dump(dir: &str, prefix: &str, frames: &[Frame]) -> io::Result<()> {
+    fs::create_dir_all(dir)?;
+    for (i, frame) in frames.iter().enumerate() {
+        let path = Path::new(dir).join(format!("{prefix}-{i:05}.txt"));
+        fs::write(path, frame.to_string())?;
+    }
+    Ok(())
+}
+
+/// Redraws the frames on stdout, `fps` of them per second.
+pub fn replay(frames: &[Frame], fps: f64) {
+    let delay = Duration::from_secs_f64(1.0 / fps);
+    let mut out = io::stdout().lock();
+    for (i, frame) in frames.iter().enumerate() {
+        writeln!(out, "\x1b[2J\x1b[H{frame}frame {}/{}", i + 1, frames.len()).unwrap();
+        out.flush().unwrap();
+        thread::sleep(delay);
+    }
+}
+
+#[test]
+fn test_frames() {
+    use crate::Context;
+
+    let grid: Grid2D<char> = "#.\n.#\n".parse().unwrap();
+    let ctx = Context::default();
+    ctx.frame(|| panic!("frames are off"));
+    assert!(ctx.take_frames().is_empty());
+
+    let mut ctx = Context::default();
+    ctx.record_frames();
+    ctx.frame(|| Frame::new(grid.clone()).note("step 1"));
+    let frames = ctx.take_frames();
+    assert_eq!(frames.len(), 1);
+    assert_eq!(frames[0].to_string(), "#.\n.#\nstep 1\n");
+}
@@ -0,0 +1,192 @@
+use std::collections::HashMap;
+
+use crate::{
+    utils::Grid2D,
+    vec::{Rect, Vec2i},
+};
+
+/// Cells addressed by [`Vec2i`]. Lets the search and region algorithms run
+/// on bounded, wrapping, tiled and sparse grids alike.
+pub trait Grid {
+    type Cell;
+
+    fn get(&self, pos: Vec2i) -> Option<&Self::Cell>;
+    fn get_mut(&mut self, pos: Vec2i) -> Option<&mut Self::Cell>;
+
+    /// Area holding every cell, `None` when there is no such limit.
+    fn bounds(&self) -> Option<Rect>;
+
+    /// The position `pos` is stored at, for grids where several positions
+    /// mean the same cell. `None` if there is no cell at `pos`.
+    fn canonical(&self, pos: Vec2i) -> Option<Vec2i> {
+        self.get(pos).map(|_| pos)
+    }
+
+    /// The cells at `pos + dir` for each of `dirs`, skipping missing ones.
+    fn neighbors_in<'a, D>(
+        &'a self,
+        pos: Vec2i,
+        dirs: D,
+    ) -> impl Iterator<Item = (Vec2i, &'a Self::Cell)> + 'a
+    where
+        D: IntoIterator<Item = Vec2i>,
+        D::IntoIter: 'a,
+    {
+        dirs.into_iter().filter_map(move |dir| {
+            let next = self.canonical(pos + dir)?;
+            self.get(next).map(|cell| (next, cell))
+        })
+    }
+
+    fn neighbors4(&self, pos: Vec2i) -> impl Iterator<Item = (Vec2i, &Self::Cell)> {
+        self.neighbors_in(pos, Vec2i::directions_4())
+    }
+
+    fn neighbors8(&self, pos: Vec2i) -> impl Iterator<Item = (Vec2i, &Self::Cell)> {
+        self.neighbors_in(pos, Vec2i::directions_8())
+    }
+
+    /// Calls `f` with each cell at `pos + dir`. A callback rather than an
+    /// iterator, as `dirs` may well repeat a direction.
+    fn neighbors_in_mut<D, F>(&mut self, pos: Vec2i, dirs: D, mut f: F)
+    where
+        D: IntoIterator<Item = Vec2i>,
+        F: FnMut(Vec2i, &mut Self::Cell),
+    {
+        for dir in dirs {
+            let Some(next) = self.canonical(pos + dir) else {
+                continue;
+            };
+            if let Some(cell) = self.get_mut(next) {
+                f(next, cell);
+            }
+        }
+    }
+
+    fn neighbors4_mut<F>(&mut self, pos: Vec2i, f: F)
+    where
+        F: FnMut(Vec2i, &mut Self::Cell),
+    {
+        self.neighbors_in_mut(pos, Vec2i::directions_4(), f)
+    }
+
+    fn neighbors8_mut<F>(&mut self, pos: Vec2i, f: F)
+    where
+        F: FnMut(Vec2i, &mut Self::Cell),
+    {
+        self.neighbors_in_mut(pos, Vec2i::directions_8(), f)
+    }
+}
+
+impl<T> Grid for Grid2D<T> {
+    type Cell = T;
+
+    fn get(&self, pos: Vec2i) -> Option<&T> {
+        Grid2D::get(self, pos)
+    }
+
+    fn get_mut(&mut self, pos: Vec2i) -> Option<&mut T> {
+        Grid2D::get_mut(self, pos)
+    }
+
+    fn bounds(&self) -> Option<Rect> {
+        let max = Vec2i::new(self.width() - 1, self.height() - 1);
+        Some(Rect::new(Vec2i::new(0, 0), max))
+    }
+}
+
+/// A sparse, unbounded grid, cells are wherever there is an entry.
+impl<T> Grid for HashMap<Vec2i, T> {
+    type Cell = T;
+
+    fn get(&self, pos: Vec2i) -> Option<&T> {
+        HashMap::get(self, &pos)
+    }
+
+    fn get_mut(&mut self, pos: Vec2i) -> Option<&mut T> {
+        HashMap::get_mut(self, &pos)
+    }
+
+    /// The bounding box of the entries, found by going through all of them.
+    fn bounds(&self) -> Option<Rect> {
+        Rect::bounding(self.keys().copied())
+    }
+}
+
+/// Makes a bounded grid wrap around at its edges, like a torus. Positions
+/// outside the bounds are folded back in, so neighbours are always inside.
+pub struct Wrapping<G>(pub G);
+
+/// Repeats a bounded grid endlessly in every direction. All copies share
+/// their cells, changing one changes them all.
+pub struct Tiled<G>(pub G);
+
+/// `pos` folded into `bounds`, `None` for empty bounds.
+fn wrap(bounds: Option<Rect>, pos: Vec2i) -> Option<Vec2i> {
+    let bounds = bounds.filter(|b| b.area() > 0)?;
+    let x = (pos.x - bounds.min.x).rem_euclid(bounds.width()) + bounds.min.x;
+    let y = (pos.y - bounds.min.y).rem_euclid(bounds.height()) + bounds.min.y;
+    Some(Vec2i::new(x, y))
+}
+
+impl<G: Grid> Grid for Wrapping<G> {
+    type Cell = G::Cell;
+
+    fn get(&self, pos: Vec2i) -> Option<&G::Cell> {
+        self.0.get(wrap(self.0.bounds(), pos)?)
+    }
+
+    fn get_mut(&mut self, pos: Vec2i) -> Option<&mut G::Cell> {
+        let pos = wrap(self.0.bounds(), pos)?;
+        self.0.get_mut(pos)
+    }
+
+    fn bounds(&self) -> Option<Rect> {
+        self.0.bounds()
+    }
+
+    fn canonical(&self, pos: Vec2i) -> Option<Vec2i> {
+        self.0.canonical(wrap(self.0.bounds(), pos)?)
+    }
+}
+
+impl<G: Grid> Grid for Tiled<G> {
+    type Cell = G::Cell;
+
+    fn get(&self, pos: Vec2i) -> Option<&G::Cell> {
+        self.0.get(wrap(self.0.bounds(), pos)?)
+    }
+
+    fn get_mut(&mut self, pos: Vec2i) -> Option<&mut G::Cell> {
+        let pos = wrap(self.0.bounds(), pos)?;
+        self.0.get_mut(pos)
+    }
+
+    fn bounds(&self) -> Option<Rect> {
+        None
+    }
+}
+
+#[test]
+fn test_grids() {
+    let mut grid: Grid2D<u32> =
+        Grid2D::parse_with("123\n456\n", |c| c.to_digit(10).ok_or(())).unwrap();
+    let sum: u32 = grid.neighbors4(Vec2i::new(0, 0)).map(|(_, n)| n).sum();
+    assert_eq!(sum, 2 + 4);
+    assert_eq!(grid.neighbors8(Vec2i::new(1, 1)).count(), 5);
+
+    let wrapping = Wrapping(grid.clone());
+    let left = wrapping.neighbors_in(Vec2i::new(0, 0), [Vec2i::new(-1, 0)]);
+    assert_eq!(left.collect::<Vec<_>>(), [(Vec2i::new(2, 0), &3)]);
+
+    let tiled = Tiled(grid.clone());
+    assert_eq!(tiled.get(Vec2i::new(-1, 3)), Some(&6));
+    assert_eq!(tiled.neighbors8(Vec2i::new(-10, 7)).count(), 8);
+
+    let sparse = HashMap::from([(Vec2i::new(5, 5), 'a'), (Vec2i::new(7, 5), 'b')]);
+    assert_eq!(sparse.neighbors4(Vec2i::new(6, 5)).count(), 2);
+    assert_eq!(Grid::bounds(&sparse).unwrap().width(), 3);
+
+    grid.neighbors4_mut(Vec2i::new(2, 1), |_, n| *n = 0);
+    assert_eq!(grid.iter().sum::<u32>(), 1 + 2 + 4 + 6);
+}
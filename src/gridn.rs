@@ -0,0 +1,296 @@
+use std::{
+    fmt::{self, Display, Formatter},
+    ops::{Index, IndexMut},
+    slice::{Iter, IterMut},
+    str::FromStr,
+};
+
+use crate::{
+    utils::{Grid2D, GridParseError},
+    SolveError,
+};
+
+/// Like [`Grid2D`], in `N` dimensions. Positions are `[i32; N]`, the first
+/// coordinate varies fastest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GridN<T, const N: usize> {
+    dims: [i32; N],
+    data: Vec<T>,
+}
+
+/// Positions are `[x, y, z]`.
+pub type Grid3D<T> = GridN<T, 3>;
+
+impl<T, const N: usize> GridN<T, N> {
+    pub fn new(dims: [i32; N], value: T) -> Self
+    where
+        T: Clone,
+    {
+        let len = dims.iter().product::<i32>().max(0) as usize;
+        Self {
+            dims,
+            data: vec![value; len],
+        }
+    }
+
+    /// Size along each axis.
+    pub fn dims(&self) -> [i32; N] {
+        self.dims
+    }
+
+    fn offset(&self, idx: [i32; N]) -> Option<usize> {
+        let mut offset = 0;
+        for axis in (0..N).rev() {
+            if !(0..self.dims[axis]).contains(&idx[axis]) {
+                return None;
+            }
+            offset = offset * self.dims[axis] as usize + idx[axis] as usize;
+        }
+        Some(offset)
+    }
+
+    fn position(&self, mut offset: usize) -> [i32; N] {
+        std::array::from_fn(|axis| {
+            let dim = self.dims[axis] as usize;
+            let coord = (offset % dim) as i32;
+            offset /= dim;
+            coord
+        })
+    }
+
+    pub fn get(&self, idx: [i32; N]) -> Option<&T> {
+        self.offset(idx).map(|i| &self.data[i])
+    }
+
+    pub fn get_mut(&mut self, idx: [i32; N]) -> Option<&mut T> {
+        self.offset(idx).map(|i| &mut self.data[i])
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.data.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        self.data.iter_mut()
+    }
+
+    pub fn iter_indices(&self) -> impl Iterator<Item = [i32; N]> + '_ {
+        (0..self.data.len()).map(|i| self.position(i))
+    }
+
+    pub fn map<R, F>(self, f: F) -> GridN<R, N>
+    where
+        F: FnMut(T) -> R,
+    {
+        GridN {
+            dims: self.dims,
+            data: self.data.into_iter().map(f).collect(),
+        }
+    }
+
+    pub fn index_of<F>(&self, predicate: F) -> Option<[i32; N]>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let i = self.data.iter().position(predicate)?;
+        Some(self.position(i))
+    }
+
+    /// The in-bounds cells at `idx + dir` for each of `dirs`.
+    pub fn neighbors_in<'a, D>(
+        &'a self,
+        idx: [i32; N],
+        dirs: D,
+    ) -> impl Iterator<Item = ([i32; N], &'a T)> + 'a
+    where
+        D: IntoIterator<Item = [i32; N]>,
+        D::IntoIter: 'a,
+    {
+        dirs.into_iter().filter_map(move |dir| {
+            let next: [i32; N] = std::array::from_fn(|axis| idx[axis] + dir[axis]);
+            self.get(next).map(|cell| (next, cell))
+        })
+    }
+
+    /// Neighbours sharing a face, 6 in 3D.
+    pub fn neighbors_orthogonal(&self, idx: [i32; N]) -> impl Iterator<Item = ([i32; N], &T)> {
+        self.neighbors_in(idx, orthogonal_directions())
+    }
+
+    /// Neighbours sharing a face, an edge or a corner, 26 in 3D.
+    pub fn neighbors_all(&self, idx: [i32; N]) -> impl Iterator<Item = ([i32; N], &T)> {
+        self.neighbors_in(idx, all_directions())
+    }
+}
+
+/// One step forwards and backwards along each axis.
+pub fn orthogonal_directions<const N: usize>() -> impl Iterator<Item = [i32; N]> {
+    (0..N).flat_map(|axis| {
+        [1, -1].map(|step| std::array::from_fn(|a| if a == axis { step } else { 0 }))
+    })
+}
+
+/// Every combination of -1, 0 and 1 but all zeros.
+pub fn all_directions<const N: usize>() -> impl Iterator<Item = [i32; N]> {
+    (0..3usize.pow(N as u32))
+        .map(|mut i| {
+            std::array::from_fn(|_| {
+                let step = (i % 3) as i32 - 1;
+                i /= 3;
+                step
+            })
+        })
+        .filter(|dir: &[i32; N]| dir.iter().any(|&d| d != 0))
+}
+
+impl<T> Grid3D<T> {
+    /// Parses layers of equal size separated by blank lines, the first
+    /// layer has `z = 0`. See [`Grid2D::parse_with`] for the layers.
+    pub fn parse_layers_with<E, F>(input: &str, mut f: F) -> Result<Self, LayersParseError<E>>
+    where
+        F: FnMut(char) -> Result<T, E>,
+    {
+        let mut layers: Vec<Grid2D<T>> = Vec::new();
+        let mut in_layer = false;
+        for (i, line) in input.lines().enumerate() {
+            let blank = line.trim().is_empty();
+            if !blank && !in_layer {
+                let offset = line.as_ptr() as usize - input.as_ptr() as usize;
+                let layer =
+                    Grid2D::parse_with(&input[offset..], &mut f).map_err(|e| e.shift_lines(i))?;
+
+                let size = |l: &Grid2D<T>| (l.width(), l.height());
+                if let Some(first) = layers.first().filter(|&f| size(f) != size(&layer)) {
+                    return Err(LayersParseError::LayerSize {
+                        line: i + 1,
+                        expected: size(first),
+                        actual: size(&layer),
+                    });
+                }
+                layers.push(layer);
+            }
+            in_layer = !blank;
+        }
+
+        let first = layers.first().ok_or(GridParseError::Empty)?;
+        let dims = [first.width(), first.height(), layers.len() as i32];
+        Ok(Self {
+            dims,
+            data: layers.into_iter().flatten().collect(),
+        })
+    }
+}
+
+/// Why [`Grid3D::parse_layers_with`] failed, `line` numbers are 1-based and
+/// count every line of the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LayersParseError<E> {
+    /// A layer that is no valid grid by itself, or no layer at all.
+    Layer(GridParseError<E>),
+    /// A layer sized unlike the first one, sizes are `(width, height)`.
+    LayerSize {
+        line: usize,
+        expected: (i32, i32),
+        actual: (i32, i32),
+    },
+}
+
+impl<E> From<GridParseError<E>> for LayersParseError<E> {
+    fn from(e: GridParseError<E>) -> Self {
+        LayersParseError::Layer(e)
+    }
+}
+
+impl<E: Display> LayersParseError<E> {
+    /// Converts to a [`SolveError`] pointing at the problem in `input`.
+    pub fn locate(&self, input: &str) -> SolveError {
+        match self {
+            LayersParseError::Layer(e) => e.locate(input),
+            LayersParseError::LayerSize {
+                line,
+                expected,
+                actual,
+            } => SolveError::new(format!(
+                "layer is {}x{}, expected {}x{}",
+                actual.0, actual.1, expected.0, expected.1
+            ))
+            .at_line(input, *line, 1),
+        }
+    }
+}
+
+impl<E: Display> Display for LayersParseError<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            LayersParseError::Layer(e) => e.fmt(f),
+            LayersParseError::LayerSize {
+                line,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "layer on line {line} is {}x{}, expected {}x{}",
+                actual.0, actual.1, expected.0, expected.1
+            ),
+        }
+    }
+}
+
+impl<E: Display + fmt::Debug> std::error::Error for LayersParseError<E> {}
+
+impl<T: TryFrom<char>> FromStr for Grid3D<T> {
+    type Err = LayersParseError<T::Error>;
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        Self::parse_layers_with(input, T::try_from)
+    }
+}
+
+impl<T, const N: usize> Index<[i32; N]> for GridN<T, N> {
+    type Output = T;
+    fn index(&self, index: [i32; N]) -> &Self::Output {
+        self.get(index).unwrap_or_else(|| {
+            panic!(
+                "Index {:?} is outside the grid dimensions {:?}",
+                index, self.dims
+            )
+        })
+    }
+}
+
+impl<T, const N: usize> IndexMut<[i32; N]> for GridN<T, N> {
+    fn index_mut(&mut self, index: [i32; N]) -> &mut Self::Output {
+        let dims = self.dims;
+        self.get_mut(index).unwrap_or_else(|| {
+            panic!(
+                "Index {:?} is outside the grid dimensions {:?}",
+                index, dims
+            )
+        })
+    }
+}
+
+#[test]
+fn test_grid3d() {
+    let grid: Grid3D<char> = "\n.#.\n...\n\n..#\n#..\n".parse().unwrap();
+    assert_eq!(grid.dims(), [3, 2, 2]);
+    assert_eq!(grid[[2, 0, 1]], '#');
+    assert_eq!(grid.index_of(|&c| c == '#'), Some([1, 0, 0]));
+    assert_eq!(grid.iter_indices().nth(7), Some([1, 0, 1]));
+
+    assert_eq!(all_directions::<3>().count(), 26);
+    assert_eq!(grid.neighbors_orthogonal([1, 1, 0]).count(), 4);
+    assert_eq!(grid.neighbors_all([1, 0, 0]).count(), 11);
+    let walls = grid
+        .neighbors_all([1, 1, 1])
+        .filter(|(_, &c)| c == '#')
+        .count();
+    assert_eq!(walls, 3);
+
+    let err = "..\n..\n\n...\n...\n".parse::<Grid3D<char>>().unwrap_err();
+    assert_eq!(err.to_string(), "layer on line 4 is 3x2, expected 2x2");
+    let err = Grid3D::parse_layers_with("..\n..\n\n.x\n..\n", |c| match c {
+        '.' => Ok(false),
+        _ => Err("not a dot"),
+    });
+    assert!(matches!(err, Err(LayersParseError::Layer(GridParseError::Cell(e))) if e.line == 4));
+}
@@ -0,0 +1,262 @@
+use std::{
+    fmt::{self, Display, Formatter},
+    ops::{Add, Index, IndexMut, Mul, Sub},
+    str::FromStr,
+};
+
+use crate::{utils::Grid2D, vec::Vec2i};
+
+/// Axial coordinates of a flat-topped hexagon, `q` grows to the south-east
+/// and `r` to the south. The third cube coordinate is [`Hex::s`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Default)]
+pub struct Hex {
+    pub q: i32,
+    pub r: i32,
+}
+
+/// The six neighbours of a flat-topped hexagon.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum HexDir {
+    N,
+    NE,
+    SE,
+    S,
+    SW,
+    NW,
+}
+
+impl Hex {
+    pub fn new(q: i32, r: i32) -> Self {
+        Self { q, r }
+    }
+
+    pub fn s(&self) -> i32 {
+        -self.q - self.r
+    }
+
+    /// Number of steps between the two hexagons.
+    pub fn distance(&self, other: &Self) -> i32 {
+        let d = *self - *other;
+        (d.q.abs() + d.r.abs() + d.s().abs()) / 2
+    }
+
+    pub fn neighbors(&self) -> impl Iterator<Item = Hex> {
+        let hex = *self;
+        HexDir::ALL.into_iter().map(move |dir| hex + dir.offset())
+    }
+
+    /// Converts to "odd-q" offset coordinates, columns with an odd x are
+    /// shifted half a cell down.
+    pub fn to_offset(self) -> Vec2i {
+        Vec2i::new(self.q, self.r + (self.q - (self.q & 1)) / 2)
+    }
+
+    pub fn from_offset(pos: Vec2i) -> Self {
+        Self::new(pos.x, pos.y - (pos.x - (pos.x & 1)) / 2)
+    }
+}
+
+impl HexDir {
+    pub const ALL: [HexDir; 6] = [
+        HexDir::N,
+        HexDir::NE,
+        HexDir::SE,
+        HexDir::S,
+        HexDir::SW,
+        HexDir::NW,
+    ];
+
+    pub fn offset(&self) -> Hex {
+        match self {
+            HexDir::N => Hex::new(0, -1),
+            HexDir::NE => Hex::new(1, -1),
+            HexDir::SE => Hex::new(1, 0),
+            HexDir::S => Hex::new(0, 1),
+            HexDir::SW => Hex::new(-1, 1),
+            HexDir::NW => Hex::new(-1, 0),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            HexDir::N => "n",
+            HexDir::NE => "ne",
+            HexDir::SE => "se",
+            HexDir::S => "s",
+            HexDir::SW => "sw",
+            HexDir::NW => "nw",
+        }
+    }
+
+    /// Parses comma separated directions like `ne,se,sw`.
+    pub fn parse_path(path: &str) -> Result<Vec<HexDir>, String> {
+        path.trim().split(',').map(|d| d.trim().parse()).collect()
+    }
+}
+
+impl Display for HexDir {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for HexDir {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        HexDir::ALL
+            .into_iter()
+            .find(|d| d.name().eq_ignore_ascii_case(s))
+            .ok_or_else(|| format!("unknown hex direction {s:?}"))
+    }
+}
+
+impl Add for Hex {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.q + other.q, self.r + other.r)
+    }
+}
+
+impl Add<HexDir> for Hex {
+    type Output = Self;
+
+    fn add(self, dir: HexDir) -> Self {
+        self + dir.offset()
+    }
+}
+
+impl Sub for Hex {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.q - other.q, self.r - other.r)
+    }
+}
+
+impl Mul<i32> for Hex {
+    type Output = Self;
+
+    fn mul(self, scalar: i32) -> Self {
+        Self::new(self.q * scalar, self.r * scalar)
+    }
+}
+
+impl Display for Hex {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("").field(&self.q).field(&self.r).finish()
+    }
+}
+
+/// Flat-topped hexagons stored in a [`Grid2D`] by their offset coordinates,
+/// see [`Hex::to_offset`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HexGrid<T> {
+    cells: Grid2D<T>,
+}
+
+impl<T> HexGrid<T> {
+    pub fn new(width: i32, height: i32, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            cells: Grid2D::new(width, height, value),
+        }
+    }
+
+    /// Takes `cells` as laid out in offset coordinates.
+    pub fn from_offset_grid(cells: Grid2D<T>) -> Self {
+        Self { cells }
+    }
+
+    pub fn offset_grid(&self) -> &Grid2D<T> {
+        &self.cells
+    }
+
+    pub fn width(&self) -> i32 {
+        self.cells.width()
+    }
+
+    pub fn height(&self) -> i32 {
+        self.cells.height()
+    }
+
+    pub fn get(&self, hex: Hex) -> Option<&T> {
+        self.cells.get(hex.to_offset())
+    }
+
+    pub fn get_mut(&mut self, hex: Hex) -> Option<&mut T> {
+        self.cells.get_mut(hex.to_offset())
+    }
+
+    pub fn iter_hexes(&self) -> impl Iterator<Item = Hex> {
+        self.cells.iter_vec2().map(Hex::from_offset)
+    }
+
+    /// The neighbours of `hex` inside the grid.
+    pub fn neighbors(&self, hex: Hex) -> impl Iterator<Item = (Hex, &T)> {
+        hex.neighbors()
+            .filter_map(|next| self.get(next).map(|cell| (next, cell)))
+    }
+}
+
+impl<T> Index<Hex> for HexGrid<T> {
+    type Output = T;
+    fn index(&self, hex: Hex) -> &Self::Output {
+        self.get(hex)
+            .unwrap_or_else(|| panic!("{hex:?} is outside the hex grid"))
+    }
+}
+
+impl<T> IndexMut<Hex> for HexGrid<T> {
+    fn index_mut(&mut self, hex: Hex) -> &mut Self::Output {
+        self.get_mut(hex)
+            .unwrap_or_else(|| panic!("{hex:?} is outside the hex grid"))
+    }
+}
+
+/// Draws the columns staggered as they are on the map, odd columns half a
+/// row lower. Lines up best with single character cells.
+impl<T: Display> Display for HexGrid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let lines = self.height() * 2 - i32::from(self.width() < 2);
+        for line in 0..lines {
+            let mut text = String::new();
+            for x in 0..self.width() {
+                let shifted = line - (x & 1);
+                match self.cells.get((x, shifted / 2)) {
+                    Some(cell) if shifted % 2 == 0 => text += &format!("{cell} "),
+                    _ => text += "  ",
+                }
+            }
+            writeln!(f, "{}", text.trim_end())?;
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn test_hex() {
+    let walk = |path: &str| {
+        let steps = HexDir::parse_path(path).unwrap();
+        let end = steps.into_iter().fold(Hex::default(), |hex, dir| hex + dir);
+        end.distance(&Hex::default())
+    };
+    assert_eq!(walk("ne,ne,ne"), 3);
+    assert_eq!(walk("ne,ne,sw,sw"), 0);
+    assert_eq!(walk("ne,ne,s,s"), 2);
+    assert_eq!(walk("se,sw,se,sw,sw"), 3);
+    assert!(HexDir::parse_path("ne,up").is_err());
+
+    for hex in [Hex::new(3, -2), Hex::new(-1, 4), Hex::new(0, 0)] {
+        assert_eq!(Hex::from_offset(hex.to_offset()), hex);
+    }
+
+    let grid = HexGrid::from_offset_grid("abc\ndef\n".parse::<Grid2D<char>>().unwrap());
+    assert_eq!(grid.to_string(), "a   c\n  b\nd   f\n  e\n");
+    let b = Hex::from_offset(Vec2i::new(1, 0));
+    let mut around: Vec<_> = grid.neighbors(b).map(|(_, &c)| c).collect();
+    around.sort();
+    assert_eq!(around, ['a', 'c', 'd', 'e', 'f']);
+}
@@ -0,0 +1,251 @@
+use std::{
+    fs,
+    io::{self, Write},
+    path::Path,
+};
+
+use crate::utils::Grid2D;
+
+pub type Rgb = [u8; 3];
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Format {
+    /// Binary color netpbm.
+    Ppm,
+    /// Binary grayscale netpbm, the colors are turned into their luma.
+    Pgm,
+    Png,
+}
+
+impl Format {
+    pub fn from_extension(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "ppm" => Some(Format::Ppm),
+            "pgm" => Some(Format::Pgm),
+            "png" => Some(Format::Png),
+            _ => None,
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Format::Ppm => "ppm",
+            Format::Pgm => "pgm",
+            Format::Png => "png",
+        }
+    }
+}
+
+/// RGB pixels in row order, mostly made from a grid with each cell drawn
+/// as a `scale` by `scale` square.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Image {
+    pub fn from_grid<T, F>(grid: &Grid2D<T>, scale: usize, mut color: F) -> Self
+    where
+        F: FnMut(&T) -> Rgb,
+    {
+        let width = grid.width() as usize * scale;
+        let mut pixels = Vec::with_capacity(width * grid.height() as usize * scale);
+        for row in grid.rows() {
+            let line: Vec<Rgb> = row
+                .iter()
+                .flat_map(|cell| [color(cell)].repeat(scale))
+                .collect();
+            for _ in 0..scale {
+                pixels.extend_from_slice(&line);
+            }
+        }
+        Self {
+            width,
+            height: grid.height() as usize * scale,
+            pixels,
+        }
+    }
+
+    /// Colors the cells by `value` with [`heat`], scaled so the smallest
+    /// value is cold and the largest hot.
+    pub fn heatmap<T, F>(grid: &Grid2D<T>, scale: usize, value: F) -> Self
+    where
+        F: Fn(&T) -> f64,
+    {
+        let (min, max) = grid
+            .iter()
+            .map(&value)
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), v| {
+                (lo.min(v), hi.max(v))
+            });
+        let range = if max > min { max - min } else { 1.0 };
+        Self::from_grid(grid, scale, |cell| heat((value(cell) - min) / range))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn pixel(&self, x: usize, y: usize) -> Rgb {
+        self.pixels[y * self.width + x]
+    }
+
+    pub fn write(&self, out: &mut impl Write, format: Format) -> io::Result<()> {
+        match format {
+            Format::Ppm => {
+                write!(out, "P6\n{} {}\n255\n", self.width, self.height)?;
+                out.write_all(self.pixels.as_flattened())
+            }
+            Format::Pgm => {
+                write!(out, "P5\n{} {}\n255\n", self.width, self.height)?;
+                let gray: Vec<u8> = self.pixels.iter().map(|&p| luma(p)).collect();
+                out.write_all(&gray)
+            }
+            Format::Png => self.write_png(out),
+        }
+    }
+
+    /// Writes to `path` in the format matching its extension.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        let format = Format::from_extension(path).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("no image format for {}", path.display()),
+            )
+        })?;
+        let mut out = io::BufWriter::new(fs::File::create(path)?);
+        self.write(&mut out, format)?;
+        out.flush()
+    }
+
+    /// 8 bit RGB, the image data deflated with stored blocks only, so the
+    /// files are big but need no compressor.
+    fn write_png(&self, out: &mut impl Write) -> io::Result<()> {
+        let mut raw = Vec::with_capacity((self.width * 3 + 1) * self.height);
+        for row in self.pixels.chunks_exact(self.width.max(1)) {
+            raw.push(0);
+            raw.extend_from_slice(row.as_flattened());
+        }
+
+        let mut zlib = vec![0x78, 0x01];
+        let mut blocks = raw.chunks(0xffff).peekable();
+        if blocks.peek().is_none() {
+            zlib.extend_from_slice(&[1, 0, 0, 0xff, 0xff]);
+        }
+        while let Some(block) = blocks.next() {
+            let len = block.len() as u16;
+            zlib.push(u8::from(blocks.peek().is_none()));
+            zlib.extend_from_slice(&len.to_le_bytes());
+            zlib.extend_from_slice(&(!len).to_le_bytes());
+            zlib.extend_from_slice(block);
+        }
+        zlib.extend_from_slice(&adler32(&raw).to_be_bytes());
+
+        let mut header = Vec::with_capacity(13);
+        header.extend_from_slice(&(self.width as u32).to_be_bytes());
+        header.extend_from_slice(&(self.height as u32).to_be_bytes());
+        header.extend_from_slice(&[8, 2, 0, 0, 0]);
+
+        out.write_all(b"\x89PNG\r\n\x1a\n")?;
+        write_chunk(out, b"IHDR", &header)?;
+        write_chunk(out, b"IDAT", &zlib)?;
+        write_chunk(out, b"IEND", &[])
+    }
+}
+
+fn write_chunk(out: &mut impl Write, kind: &[u8; 4], data: &[u8]) -> io::Result<()> {
+    out.write_all(&(data.len() as u32).to_be_bytes())?;
+    out.write_all(kind)?;
+    out.write_all(data)?;
+    let crc = crc32(kind.iter().chain(data));
+    out.write_all(&crc.to_be_bytes())
+}
+
+fn crc32<'a>(bytes: impl IntoIterator<Item = &'a u8>) -> u32 {
+    let mut crc = !0u32;
+    for &byte in bytes {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+fn adler32(bytes: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in bytes {
+        a = (a + byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    (b << 16) | a
+}
+
+fn luma([r, g, b]: Rgb) -> u8 {
+    ((r as u32 * 299 + g as u32 * 587 + b as u32 * 114) / 1000) as u8
+}
+
+/// Runs from dark blue at 0 through green and yellow to red at 1, values
+/// outside are clamped.
+pub fn heat(t: f64) -> Rgb {
+    const STOPS: [Rgb; 5] = [
+        [0, 0, 96],
+        [0, 128, 255],
+        [0, 200, 0],
+        [255, 220, 0],
+        [220, 0, 0],
+    ];
+    let t = t.clamp(0.0, 1.0) * (STOPS.len() - 1) as f64;
+    let i = (t as usize).min(STOPS.len() - 2);
+    let f = t - i as f64;
+    std::array::from_fn(|c| {
+        let (a, b) = (STOPS[i][c] as f64, STOPS[i + 1][c] as f64);
+        (a + (b - a) * f).round() as u8
+    })
+}
+
+/// Writes every image to its own file `{dir}/{prefix}-00000.png`, ...,
+/// like [`crate::frames::dump`].
+pub fn dump(dir: &str, prefix: &str, images: &[Image], format: Format) -> io::Result<()> {
+    fs::create_dir_all(dir)?;
+    for (i, image) in images.iter().enumerate() {
+        let path = Path::new(dir).join(format!("{prefix}-{i:05}.{}", format.extension()));
+        image.save(path)?;
+    }
+    Ok(())
+}
+
+#[test]
+fn test_image() {
+    assert_eq!(crc32(b"IEND"), 0xae42_6082);
+    assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+
+    let grid: Grid2D<u32> = Grid2D::parse_with("09\n45\n", |c| c.to_digit(10).ok_or(())).unwrap();
+    let image = Image::heatmap(&grid, 2, |&n| n as f64);
+    assert_eq!((image.width(), image.height()), (4, 4));
+    assert_eq!(image.pixel(0, 1), heat(0.0));
+    assert_eq!(image.pixel(3, 0), heat(1.0));
+
+    let mut pgm = Vec::new();
+    image.write(&mut pgm, Format::Pgm).unwrap();
+    assert_eq!(pgm.len(), "P5\n4 4\n255\n".len() + 16);
+
+    let mut png = Vec::new();
+    image.write(&mut png, Format::Png).unwrap();
+    assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+    let idat = 8 + 12 + 13;
+    assert_eq!(&png[idat + 4..idat + 8], b"IDAT");
+    let raw_len = 4 * (1 + 4 * 3);
+    assert_eq!(png.len(), idat + 12 + 2 + 5 + raw_len + 4 + 12);
+}
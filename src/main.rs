@@ -1,5 +1,9 @@
 #![allow(unused)]
 
+mod answer;
+mod automaton;
+mod bitgrid;
+mod context;
 mod day01;
 mod day02;
 mod day03;
@@ -12,50 +16,281 @@ mod day09;
 mod day10;
 mod day11;
 mod day12;
+mod error;
+mod frames;
+mod grid;
+mod gridn;
+mod hex;
+mod image;
+mod progress;
+mod regions;
+mod render;
+mod report;
+mod search;
 mod utils;
 mod vec;
 
 use std::{
-    fmt::{Display, Write},
-    fs, thread,
+    any::Any,
+    collections::HashSet,
+    env,
+    fmt::{Display, Formatter, Write},
+    fs, process,
+    str::FromStr,
+    sync::Arc,
+    thread,
+    time::{Duration, Instant},
 };
 
-pub enum Answer {
-    Int(i64),
+use progress::ProgressLine;
+use report::{DayReport, Outcome, PartReport};
+
+pub use answer::Answer;
+pub use context::{Context, Param};
+pub use error::{SolveError, SolveResult};
+pub use frames::Frame;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+    /// Extra part named by [`Solution::bonus_parts`].
+    Bonus(&'static str),
 }
-impl From<i64> for Answer {
-    fn from(value: i64) -> Self {
-        Self::Int(value)
+impl Display for Part {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Part::One => f.write_str("one"),
+            Part::Two => f.write_str("two"),
+            Part::Bonus(name) => f.write_str(name),
+        }
     }
 }
-impl From<i32> for Answer {
-    fn from(value: i32) -> Self {
-        Self::Int(value as _)
+
+/// Rough category of a puzzle, used to list and filter solutions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tag {
+    Grid,
+    Graph,
+    Simulation,
+    Parsing,
+    Dp,
+}
+impl Tag {
+    const ALL: [Tag; 5] = [
+        Tag::Grid,
+        Tag::Graph,
+        Tag::Simulation,
+        Tag::Parsing,
+        Tag::Dp,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Tag::Grid => "grid",
+            Tag::Graph => "graph",
+            Tag::Simulation => "simulation",
+            Tag::Parsing => "parsing",
+            Tag::Dp => "dp",
+        }
     }
 }
-impl From<usize> for Answer {
-    fn from(value: usize) -> Self {
-        Self::Int(value as _)
+impl Display for Tag {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
     }
 }
-impl From<u64> for Answer {
-    fn from(value: u64) -> Self {
-        Self::Int(value as _)
+impl FromStr for Tag {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Tag::ALL.into_iter().find(|t| t.name() == s).ok_or_else(|| {
+            let known: Vec<_> = Tag::ALL.iter().map(Tag::name).collect();
+            format!("unknown tag {s:?}, expected one of {}", known.join(", "))
+        })
     }
 }
-impl Display for Answer {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Answer::Int(val) => val.fmt(f),
+
+/// Type-erased result of [`Solution::parse_input`].
+pub type ParsedInput = Box<dyn Any + Send + Sync>;
+
+pub trait Solution: Send + Sync {
+    fn day_number(&self) -> i32;
+    fn part_one(&self, input: &str, ctx: &Context) -> SolveResult;
+    fn part_two(&self, input: &str, ctx: &Context) -> SolveResult;
+
+    /// Puzzle constants the parts read through [`Context::param`].
+    fn params(&self) -> Vec<Param> {
+        Vec::new()
+    }
+
+    fn title(&self) -> Option<&'static str> {
+        None
+    }
+
+    fn tags(&self) -> &'static [Tag] {
+        &[]
+    }
+
+    /// Names of parts beyond the puzzle's two, see [`Solution::bonus_part`].
+    fn bonus_parts(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    fn bonus_part(&self, name: &str, input: &str, ctx: &Context) -> SolveResult {
+        Err(SolveError::unknown_part(name))
+    }
+
+    /// Names of other implementations of `part`, next to the primary one.
+    /// `--all-impls` runs them all and checks that they agree.
+    fn alt_impls(&self, part: Part) -> &'static [&'static str] {
+        &[]
+    }
+
+    fn alt_impl(&self, part: Part, name: &str, input: &str, ctx: &Context) -> SolveResult {
+        Err(SolveError::unknown_impl(part, name))
+    }
+
+    /// All parts in the order they run.
+    fn parts(&self) -> Vec<Part> {
+        let bonus = self.bonus_parts().iter().map(|name| Part::Bonus(name));
+        [Part::One, Part::Two].into_iter().chain(bonus).collect()
+    }
+
+    /// Whether [`Solution::parse_input`] does any parsing worth timing.
+    fn has_parse_step(&self) -> bool {
+        false
+    }
+
+    /// Used by the runner to parse the input once for both parts.
+    /// Solutions working on the raw input keep it as is.
+    fn parse_input(&self, input: &'static str) -> Result<ParsedInput, SolveError> {
+        Ok(Box::new(input))
+    }
+
+    /// Runs one part on the result of [`Solution::parse_input`].
+    fn run_part(&self, part: Part, parsed: &ParsedInput, ctx: &Context) -> SolveResult {
+        let input: &str = parsed.downcast_ref::<&str>().unwrap();
+        match part {
+            Part::One => self.part_one(input, ctx),
+            Part::Two => self.part_two(input, ctx),
+            Part::Bonus(name) => self.bonus_part(name, input, ctx),
         }
     }
+
+    /// Runs [`Solution::alt_impl`] on the result of [`Solution::parse_input`].
+    fn run_alt_impl(
+        &self,
+        part: Part,
+        name: &str,
+        parsed: &ParsedInput,
+        ctx: &Context,
+    ) -> SolveResult {
+        let input: &str = parsed.downcast_ref::<&str>().unwrap();
+        self.alt_impl(part, name, input, ctx)
+    }
 }
 
-pub trait Solution: Send {
+/// A [`Solution`] that parses its input once, both parts then borrow the
+/// parsed value.
+pub trait ParsedSolution: Send + Sync {
+    type Parsed: Send + Sync + 'static;
+
     fn day_number(&self) -> i32;
-    fn clone_dyn(&self) -> Box<dyn Solution>;
-    fn part_one(&self, input: &str) -> Answer;
-    fn part_two(&self, input: &str) -> Answer;
+    fn parse(&self, input: &str) -> Result<Self::Parsed, SolveError>;
+    fn part_one(&self, parsed: &Self::Parsed, ctx: &Context) -> SolveResult;
+    fn part_two(&self, parsed: &Self::Parsed, ctx: &Context) -> SolveResult;
+
+    fn params(&self) -> Vec<Param> {
+        Vec::new()
+    }
+
+    fn title(&self) -> Option<&'static str> {
+        None
+    }
+
+    fn tags(&self) -> &'static [Tag] {
+        &[]
+    }
+
+    fn bonus_parts(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    fn bonus_part(&self, name: &str, parsed: &Self::Parsed, ctx: &Context) -> SolveResult {
+        Err(SolveError::unknown_part(name))
+    }
+
+    fn alt_impls(&self, part: Part) -> &'static [&'static str] {
+        &[]
+    }
+
+    fn alt_impl(
+        &self,
+        part: Part,
+        name: &str,
+        parsed: &Self::Parsed,
+        ctx: &Context,
+    ) -> SolveResult {
+        Err(SolveError::unknown_impl(part, name))
+    }
+}
+
+impl<S: ParsedSolution> Solution for S {
+    fn day_number(&self) -> i32 {
+        ParsedSolution::day_number(self)
+    }
+    fn part_one(&self, input: &str, ctx: &Context) -> SolveResult {
+        ParsedSolution::part_one(self, &self.parse(input)?, ctx)
+    }
+    fn part_two(&self, input: &str, ctx: &Context) -> SolveResult {
+        ParsedSolution::part_two(self, &self.parse(input)?, ctx)
+    }
+    fn params(&self) -> Vec<Param> {
+        ParsedSolution::params(self)
+    }
+    fn title(&self) -> Option<&'static str> {
+        ParsedSolution::title(self)
+    }
+    fn tags(&self) -> &'static [Tag] {
+        ParsedSolution::tags(self)
+    }
+    fn bonus_parts(&self) -> &'static [&'static str] {
+        ParsedSolution::bonus_parts(self)
+    }
+    fn bonus_part(&self, name: &str, input: &str, ctx: &Context) -> SolveResult {
+        ParsedSolution::bonus_part(self, name, &self.parse(input)?, ctx)
+    }
+    fn alt_impls(&self, part: Part) -> &'static [&'static str] {
+        ParsedSolution::alt_impls(self, part)
+    }
+    fn alt_impl(&self, part: Part, name: &str, input: &str, ctx: &Context) -> SolveResult {
+        ParsedSolution::alt_impl(self, part, name, &self.parse(input)?, ctx)
+    }
+
+    fn has_parse_step(&self) -> bool {
+        true
+    }
+    fn parse_input(&self, input: &'static str) -> Result<ParsedInput, SolveError> {
+        Ok(Box::new(self.parse(input)?))
+    }
+    fn run_part(&self, part: Part, parsed: &ParsedInput, ctx: &Context) -> SolveResult {
+        let parsed: &S::Parsed = parsed.downcast_ref().unwrap();
+        match part {
+            Part::One => ParsedSolution::part_one(self, parsed, ctx),
+            Part::Two => ParsedSolution::part_two(self, parsed, ctx),
+            Part::Bonus(name) => ParsedSolution::bonus_part(self, name, parsed, ctx),
+        }
+    }
+    fn run_alt_impl(
+        &self,
+        part: Part,
+        name: &str,
+        parsed: &ParsedInput,
+        ctx: &Context,
+    ) -> SolveResult {
+        let parsed: &S::Parsed = parsed.downcast_ref().unwrap();
+        ParsedSolution::alt_impl(self, part, name, parsed, ctx)
+    }
 }
 
 const RST: &str = "\x1b[0m";
@@ -63,30 +298,173 @@ const RED: &str = "\x1b[31m";
 const GRN: &str = "\x1b[32m";
 const YLW: &str = "\x1b[2;33m";
 
+fn solutions() -> Vec<Arc<dyn Solution>> {
+    vec![
+        Arc::new(day01::Day01),
+        Arc::new(day02::Day02),
+        Arc::new(day03::Day03),
+        Arc::new(day04::Day04),
+        Arc::new(day05::Day05),
+        Arc::new(day06::Day06),
+        Arc::new(day07::Day07),
+        Arc::new(day08::Day08),
+        Arc::new(day09::Day09),
+        Arc::new(day10::Day10),
+        Arc::new(day11::Day11),
+        Arc::new(day12::Day12),
+    ]
+}
+
+#[derive(Default)]
+struct Args {
+    /// `--param name=value`, applied to every solution declaring `name`.
+    params: Vec<(String, String)>,
+    /// `--day N`, may be repeated.
+    days: Vec<i32>,
+    /// `--tag T`, solutions having any of the tags are selected.
+    tags: Vec<Tag>,
+    /// `--part NAME`, may be repeated, all parts run by default.
+    parts: Vec<String>,
+    /// `--all-impls`, run alternative implementations too and compare them.
+    all_impls: bool,
+    /// `--all`, without any selection only the latest day runs.
+    all: bool,
+    /// `--list` the selected solutions instead of running them.
+    list: bool,
+    /// `--json PATH` to write the results to.
+    json: Option<String>,
+    /// `--frames DIR` to write the frames pushed by the parts to.
+    frames_dir: Option<String>,
+    /// `--replay FPS`, shows the frames in the terminal.
+    replay: Option<f64>,
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut args = Args::default();
+    let mut it = env::args().skip(1);
+    while let Some(arg) = it.next() {
+        let mut value = || it.next().ok_or_else(|| format!("{arg} expects a value"));
+        match arg.as_str() {
+            "--param" => {
+                let param = value()?;
+                let (name, value) = param
+                    .split_once('=')
+                    .ok_or_else(|| format!("--param expects `name=value`, got {param:?}"))?;
+                args.params.push((name.to_owned(), value.to_owned()));
+            }
+            "--day" => {
+                let day = value()?;
+                let day = day.parse().map_err(|_| format!("invalid day {day:?}"))?;
+                args.days.push(day);
+            }
+            "--tag" => args.tags.push(value()?.parse()?),
+            "--part" => args.parts.push(value()?),
+            "--json" => args.json = Some(value()?),
+            "--frames" => args.frames_dir = Some(value()?),
+            "--replay" => {
+                let fps = value()?;
+                match fps.parse() {
+                    Ok(fps) if fps > 0.0 => args.replay = Some(fps),
+                    _ => return Err(format!("invalid frame rate {fps:?}")),
+                }
+            }
+            "--all" => args.all = true,
+            "--all-impls" => args.all_impls = true,
+            "--list" => args.list = true,
+            _ => return Err(format!("unknown argument {arg:?}")),
+        }
+    }
+    Ok(args)
+}
+
+impl Args {
+    fn select(&self, mut solutions: Vec<Arc<dyn Solution>>) -> Vec<Arc<dyn Solution>> {
+        if self.days.is_empty() && self.tags.is_empty() && !self.all && !self.list {
+            return solutions.pop().into_iter().collect();
+        }
+        solutions.retain(|sol| {
+            (self.days.is_empty() || self.days.contains(&sol.day_number()))
+                && (self.tags.is_empty() || sol.tags().iter().any(|t| self.tags.contains(t)))
+        });
+        solutions
+    }
+
+    fn wants_frames(&self) -> bool {
+        self.frames_dir.is_some() || self.replay.is_some()
+    }
+
+    fn select_parts(&self, sol: &dyn Solution) -> Vec<Part> {
+        let mut parts = sol.parts();
+        if !self.parts.is_empty() {
+            parts.retain(|p| self.parts.contains(&p.to_string()));
+        }
+        parts
+    }
+}
+
 fn main() {
-    let solutions: Vec<Box<dyn Solution>> = vec![
-        //Box::new(day01::Day01),
-        //Box::new(day02::Day02),
-        //Box::new(day03::Day03),
-        //Box::new(day04::Day04),
-        //Box::new(day05::Day05),
-        //Box::new(day06::Day06),
-        //Box::new(day07::Day07),
-        //Box::new(day08::Day08),
-        //Box::new(day09::Day09),
-        //Box::new(day10::Day10),
-        //Box::new(day11::Day11),
-        Box::new(day12::Day12),
-    ];
+    let args = parse_args().unwrap_or_else(|e| {
+        eprintln!("{RED}error{RST}: {e}");
+        process::exit(2);
+    });
 
+    let solutions = args.select(solutions());
+    if args.list {
+        for sol in &solutions {
+            let tags: Vec<_> = sol.tags().iter().map(Tag::name).collect();
+            let parts: Vec<_> = sol.parts().iter().map(Part::to_string).collect();
+            println!(
+                "day {:2}  {:24} {:18} {YLW}{}{RST}",
+                sol.day_number(),
+                sol.title().unwrap_or("-"),
+                parts.join(", "),
+                tags.join(", ")
+            );
+        }
+        return;
+    }
+
+    let mut reports = Vec::new();
+    let mut used_params = HashSet::new();
     for sol in solutions {
         let day = sol.day_number();
+        let mut report = DayReport::new(sol.as_ref());
 
         let mut msg = String::new();
         let input_file = format!("src/day{day:02}.txt");
         msg.write_fmt(format_args!("Task {YLW}{input_file}{RST}"))
             .unwrap();
 
+        let parts = args.select_parts(sol.as_ref());
+        if parts.is_empty() {
+            println!("{}, has no part {}", &msg, args.parts.join(" or "));
+            continue;
+        }
+
+        let mut ctx = Context::new(sol.params());
+        let mut param_error = None;
+        for (name, value) in &args.params {
+            if ctx.has_param(name) {
+                used_params.insert(name.as_str());
+                param_error = param_error.or(ctx.set_param(name, value).err());
+            }
+        }
+        if let Some(e) = param_error {
+            println!("{}, {RED}error{RST}: {}", &msg, e);
+            report.error = Some(e.to_string());
+            reports.push(report);
+            continue;
+        }
+        let overridden: Vec<_> = ctx.overridden().map(|(n, v)| format!("{n}={v}")).collect();
+        if !overridden.is_empty() {
+            msg.write_fmt(format_args!(" ({})", overridden.join(", ")))
+                .unwrap();
+        }
+        if args.wants_frames() {
+            ctx.record_frames();
+        }
+        let ctx = Arc::new(ctx);
+
         let input = match fs::read_to_string(&input_file) {
             Ok(s) => {
                 println!("{}, starting...", &msg);
@@ -94,36 +472,199 @@ fn main() {
             }
             Err(e) => {
                 println!("{}, no input: {}", &msg, e);
+                report.error = Some(format!("no input: {e}"));
+                reports.push(report);
                 continue;
             }
         };
         let input_ref: &'static str = input.leak();
 
-        let part1_name = format!("{YLW}{}{RST}, part one", &input_file);
-        let part1_sol = sol.clone_dyn();
-        run_guarded(part1_name.clone(), move || {
-            let result = part1_sol.part_one(input_ref);
-            println!("     {part1_name} = {GRN}{}{RST}", &result);
+        let parse_name = format!("{YLW}{}{RST}, parsing", &input_file);
+        let parse_sol = sol.clone();
+        let parsed = run_guarded(parse_name.clone(), move || {
+            let start = Instant::now();
+            let parsed = parse_sol.parse_input(input_ref);
+            let elapsed = start.elapsed();
+            let elapsed = parse_sol.has_parse_step().then_some(elapsed);
+            match (&parsed, elapsed) {
+                (Ok(_), Some(elapsed)) => println!("     {parse_name} took {elapsed:.2?}"),
+                (Ok(_), None) => {}
+                (Err(e), _) => print_error(&parse_name, e),
+            }
+            (parsed, elapsed)
         });
+        let parsed = match parsed {
+            Some((Ok(parsed), elapsed)) => {
+                report.parse_time = elapsed;
+                Arc::new(parsed)
+            }
+            Some((Err(e), _)) => {
+                report.error = Some(e.to_string());
+                reports.push(report);
+                continue;
+            }
+            None => {
+                report.error = Some("parsing panicked".to_owned());
+                reports.push(report);
+                continue;
+            }
+        };
 
-        let part2_name = format!("{YLW}{}{RST}, part two", &input_file);
-        run_guarded(part2_name.clone(), move || {
-            let result = sol.part_two(input_ref);
-            println!("     {part2_name} = {GRN}{}{RST}", &result);
-        });
+        for part in parts {
+            let primary = run_part(&input_file, &sol, part, None, &parsed, &ctx);
+            output_frames(&args, day, &primary, &ctx);
+            let mut alts = Vec::new();
+            if args.all_impls {
+                for &name in sol.alt_impls(part) {
+                    let mut alt = run_part(&input_file, &sol, part, Some(name), &parsed, &ctx);
+                    output_frames(&args, day, &alt, &ctx);
+                    compare_impls(&primary, &mut alt);
+                    alts.push(alt);
+                }
+            }
+            report.parts.push(primary);
+            report.parts.append(&mut alts);
+        }
+        reports.push(report);
+    }
+
+    for (name, _) in &args.params {
+        if !used_params.contains(name.as_str()) {
+            println!("{YLW}warning{RST}: no solution has a parameter `{name}`");
+        }
+    }
+
+    if reports.len() > 1 {
+        report::print_summary(&reports);
+    }
+    if let Some(path) = &args.json {
+        if let Err(e) = fs::write(path, report::to_json(&reports)) {
+            eprintln!("{RED}error{RST}: cannot write {path}: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+/// Runs `part`, or its alternative implementation `imp`, on its own thread.
+fn run_part(
+    input_file: &str,
+    sol: &Arc<dyn Solution>,
+    part: Part,
+    imp: Option<&'static str>,
+    parsed: &Arc<ParsedInput>,
+    ctx: &Arc<Context>,
+) -> PartReport {
+    let part_name = match imp {
+        Some(imp) => format!("{YLW}{input_file}{RST}, part {part} ({imp})"),
+        None => format!("{YLW}{input_file}{RST}, part {part}"),
+    };
+    let sol = sol.clone();
+    let parsed = parsed.clone();
+    let ctx = ctx.clone();
+    let report = run_guarded(part_name.clone(), move || {
+        ctx.progress().reset();
+        let progress = ProgressLine::start(part_name.clone(), ctx.progress_handle());
+        let before = ctx.shared_use();
+        let start = Instant::now();
+        let result = match imp {
+            Some(imp) => sol.run_alt_impl(part, imp, &parsed, &ctx),
+            None => sol.run_part(part, &parsed, &ctx),
+        };
+        let elapsed = start.elapsed();
+        drop(progress);
+        match &result {
+            Ok(answer) => print_answer(&part_name, answer, elapsed),
+            Err(e) => print_error(&part_name, e),
+        }
+        let mut report = PartReport::new(part, imp, result, elapsed);
+        let after = ctx.shared_use();
+        let shared_time = after.computed - before.computed;
+        report.shared_time = (!shared_time.is_zero()).then_some(shared_time);
+        report.reused_shared = after.reused > before.reused;
+        report
+    });
+    report.unwrap_or(PartReport {
+        part,
+        imp,
+        outcome: Outcome::Panic,
+        elapsed: None,
+        shared_time: None,
+        reused_shared: false,
+    })
+}
+
+/// Writes or replays the frames `part` pushed, as `--frames` and `--replay` ask.
+fn output_frames(args: &Args, day: i32, part: &PartReport, ctx: &Context) {
+    let frames = ctx.take_frames();
+    if frames.is_empty() {
+        return;
+    }
+    if let Some(fps) = args.replay {
+        frames::replay(&frames, fps);
+    }
+    if let Some(dir) = &args.frames_dir {
+        let prefix = match part.imp {
+            Some(imp) => format!("day{day:02}-{}-{imp}", part.part),
+            None => format!("day{day:02}-{}", part.part),
+        };
+        match frames::dump(dir, &prefix, &frames) {
+            Ok(()) => println!("     {} frames written to {dir}", frames.len()),
+            Err(e) => println!("     {RED}error{RST}: cannot write frames to {dir}: {e}"),
+        }
+    }
+}
+
+/// Turns `alt` into a failure when its answer differs from the primary one,
+/// and prints how their running times compare.
+fn compare_impls(primary: &PartReport, alt: &mut PartReport) {
+    let (Outcome::Answer(expected), Outcome::Answer(actual)) = (&primary.outcome, &alt.outcome)
+    else {
+        return;
+    };
+    let imp = alt.imp.unwrap_or_default();
+    if expected != actual {
+        let msg = format!("`{imp}` answered {actual}, the primary implementation {expected}");
+        println!("     {RED}mismatch{RST}: {msg}");
+        alt.outcome = Outcome::Error(msg);
+    } else if let (Some(a), Some(b)) = (primary.elapsed, alt.elapsed) {
+        let ratio = b.as_secs_f64() / a.as_secs_f64();
+        println!("     `{imp}` agrees, {ratio:.2}x the time of the primary implementation");
+    }
+}
+
+fn print_answer(name: &str, answer: &Answer, elapsed: Duration) {
+    let pretty = answer.pretty();
+    if pretty.contains('\n') {
+        println!("     {name} = ({elapsed:.2?})");
+        for line in pretty.lines() {
+            println!("         {GRN}{line}{RST}");
+        }
+    } else {
+        println!("     {name} = {GRN}{pretty}{RST} ({elapsed:.2?})");
+    }
+}
+
+fn print_error(name: &str, error: &SolveError) {
+    println!("     {name} = {RED}error{RST}: {error}");
+    if let Some(loc) = error.location() {
+        let margin = loc.line.to_string().len();
+        println!("         {} | {}", loc.line, loc.text);
+        println!("         {:margin$} | {:>col$}", "", "^", col = loc.column);
     }
 }
 
-fn run_guarded<F>(name: String, f: F)
+/// Runs `f` on its own thread, a panic is reported and turned into `None`.
+fn run_guarded<F, R>(name: String, f: F) -> Option<R>
 where
-    F: Fn() + Send + 'static,
+    F: FnOnce() -> R + Send + 'static,
+    R: Send + 'static,
 {
     let builder = thread::Builder::new().name(name.clone());
-    let thread = builder.spawn(move || f()).unwrap();
+    let thread = builder.spawn(f).unwrap();
 
     let result = thread.join();
-    match result {
-        Ok(_) => (),
-        Err(_) => (), //println!("     {} {RED}panicked{RST}.", &name),
+    if result.is_err() {
+        println!("     {} {RED}panicked{RST}.", &name);
     }
+    result.ok()
 }
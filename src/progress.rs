@@ -0,0 +1,126 @@
+use std::{
+    io::{self, IsTerminal, Write},
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+use crate::{RST, YLW};
+
+/// Progress of a long-running part, reported through [`crate::Context::progress`].
+/// Updates are plain atomic stores, the runner samples them a few times a
+/// second, so calling these in a hot loop is fine.
+#[derive(Default)]
+pub struct Progress {
+    done: AtomicU64,
+    total: AtomicU64,
+    message: Mutex<Option<String>>,
+}
+
+impl Progress {
+    pub fn set_total(&self, total: u64) {
+        self.total.store(total, Ordering::Relaxed);
+    }
+
+    pub fn set_done(&self, done: u64) {
+        self.done.store(done, Ordering::Relaxed);
+    }
+
+    pub fn inc(&self) {
+        self.done.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn set_message(&self, message: impl Into<String>) {
+        *self.message.lock().unwrap() = Some(message.into());
+    }
+
+    pub fn reset(&self) {
+        self.set_done(0);
+        self.set_total(0);
+        *self.message.lock().unwrap() = None;
+    }
+
+    fn render(&self) -> Option<String> {
+        let done = self.done.load(Ordering::Relaxed);
+        let total = self.total.load(Ordering::Relaxed);
+        let message = self.message.lock().unwrap();
+
+        let mut line = match total {
+            0 if done == 0 => String::new(),
+            0 => format!("{done}"),
+            _ => format!("{done}/{total} ({}%)", done * 100 / total),
+        };
+        if let Some(msg) = message.as_deref() {
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(msg);
+        }
+        (!line.is_empty()).then_some(line)
+    }
+}
+
+/// Redraws a single status line on stderr until dropped. Does nothing
+/// unless stderr is a terminal.
+pub struct ProgressLine {
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl ProgressLine {
+    const INTERVAL: Duration = Duration::from_millis(100);
+
+    pub fn start(name: String, progress: Arc<Progress>) -> Self {
+        let stop = Arc::new(AtomicBool::new(false));
+        if !io::stderr().is_terminal() {
+            return Self { stop, thread: None };
+        }
+
+        let stop_flag = stop.clone();
+        let thread = thread::spawn(move || {
+            let mut drawn = false;
+            while !stop_flag.load(Ordering::Relaxed) {
+                thread::park_timeout(Self::INTERVAL);
+                if let Some(line) = progress.render() {
+                    eprint!("\r\x1b[2K     {name} {YLW}{line}{RST}");
+                    io::stderr().flush().unwrap();
+                    drawn = true;
+                }
+            }
+            if drawn {
+                eprint!("\r\x1b[2K");
+            }
+        });
+        Self {
+            stop,
+            thread: Some(thread),
+        }
+    }
+}
+
+impl Drop for ProgressLine {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            thread.thread().unpark();
+            thread.join().unwrap();
+        }
+    }
+}
+
+#[test]
+fn test_render() {
+    let progress = Progress::default();
+    assert_eq!(progress.render(), None);
+
+    progress.set_total(200);
+    progress.inc();
+    progress.set_message("blink 1");
+    assert_eq!(progress.render().unwrap(), "1/200 (0%) blink 1");
+
+    progress.set_done(150);
+    assert_eq!(progress.render().unwrap(), "150/200 (75%) blink 1");
+}
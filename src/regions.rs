@@ -0,0 +1,241 @@
+use std::collections::VecDeque;
+
+use crate::{
+    bitgrid::BitGrid,
+    grid::Grid,
+    utils::Grid2D,
+    vec::{Rect, Vec2i},
+};
+
+#[cfg(test)]
+use crate::grid::Wrapping;
+
+/// Which cells count as adjacent when growing a region.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Connectivity {
+    Four,
+    Eight,
+}
+
+impl Connectivity {
+    fn directions(self) -> impl Iterator<Item = Vec2i> {
+        let diagonals = match self {
+            Connectivity::Four => None,
+            Connectivity::Eight => Some(Vec2i::directions_4_diag()),
+        };
+        Vec2i::directions_4().chain(diagonals.into_iter().flatten())
+    }
+
+    /// The connectivity the outside of a region has, used to find holes.
+    fn dual(self) -> Self {
+        match self {
+            Connectivity::Four => Connectivity::Eight,
+            Connectivity::Eight => Connectivity::Four,
+        }
+    }
+}
+
+/// One connected component of a [`Regions`] labelling.
+pub struct Region {
+    /// First cell of the region in row order.
+    pub start: Vec2i,
+    /// In the order they were reached, starting with `start`.
+    pub cells: Vec<Vec2i>,
+    /// Number of cell edges bordering other regions or the outside.
+    pub perimeter: usize,
+    /// Number of straight fence segments around the region, holes included.
+    pub sides: usize,
+    pub bbox: Rect,
+    connectivity: Connectivity,
+}
+
+/// Every cell of a grid labelled with the index of its region.
+pub struct Regions {
+    /// Covers the grid's bounds, `None` where the grid has no cell.
+    labels: Grid2D<Option<usize>>,
+    origin: Vec2i,
+    pub regions: Vec<Region>,
+}
+
+impl Regions {
+    /// Regions of equal cells.
+    pub fn label<G>(grid: &G, connectivity: Connectivity) -> Self
+    where
+        G: Grid,
+        G::Cell: PartialEq,
+    {
+        Self::label_by(grid, connectivity, |a, b| a == b)
+    }
+
+    /// Regions of cells `same` puts together. `same` is asked for adjacent
+    /// cells only and should be an equivalence, otherwise the outcome
+    /// depends on the order cells are visited in. Panics for grids without
+    /// bounds.
+    pub fn label_by<G, F>(grid: &G, connectivity: Connectivity, mut same: F) -> Self
+    where
+        G: Grid,
+        F: FnMut(&G::Cell, &G::Cell) -> bool,
+    {
+        let bounds = grid.bounds().expect("only bounded grids have regions");
+        let mut labels = Self {
+            labels: Grid2D::new(bounds.width(), bounds.height(), None),
+            origin: bounds.min,
+            regions: Vec::new(),
+        };
+        let mut cells = Vec::new();
+        for start in bounds.iter() {
+            if labels.label_at(start).is_some() || grid.canonical(start) != Some(start) {
+                continue;
+            }
+
+            let label = cells.len();
+            let mut region = vec![start];
+            let mut queue = VecDeque::from([start]);
+            labels.set(start, label);
+            while let Some(pos) = queue.pop_front() {
+                let cell = grid.get(pos).unwrap();
+                for (next, next_cell) in grid.neighbors_in(pos, connectivity.directions()) {
+                    if labels.label_at(next).is_none() && same(cell, next_cell) {
+                        labels.set(next, label);
+                        region.push(next);
+                        queue.push_back(next);
+                    }
+                }
+            }
+            cells.push((start, region));
+        }
+
+        labels.regions = cells
+            .into_iter()
+            .enumerate()
+            .map(|(label, (start, cells))| {
+                Region::new(grid, &labels, label, start, cells, connectivity)
+            })
+            .collect();
+        labels
+    }
+
+    /// Index of the region `pos` belongs to.
+    pub fn label_at(&self, pos: Vec2i) -> Option<usize> {
+        self.labels.get(pos - self.origin).copied().flatten()
+    }
+
+    fn set(&mut self, pos: Vec2i, label: usize) {
+        self.labels[pos - self.origin] = Some(label);
+    }
+
+    /// The region `pos` belongs to.
+    pub fn region_at(&self, pos: Vec2i) -> Option<&Region> {
+        self.label_at(pos).map(|label| &self.regions[label])
+    }
+}
+
+impl Region {
+    fn new<G: Grid>(
+        grid: &G,
+        labels: &Regions,
+        label: usize,
+        start: Vec2i,
+        cells: Vec<Vec2i>,
+        connectivity: Connectivity,
+    ) -> Self {
+        let inside = |p: Vec2i| {
+            grid.canonical(p)
+                .is_some_and(|p| labels.label_at(p) == Some(label))
+        };
+        let mut perimeter = 0;
+        let mut corners = 0;
+        for &pos in &cells {
+            for dir in Vec2i::directions_4() {
+                let dir_r = dir.rot_x2y();
+                if !inside(pos + dir) {
+                    perimeter += 1;
+                    if !inside(pos + dir_r) {
+                        corners += 1;
+                    }
+                } else if inside(pos + dir_r) && !inside(pos + dir + dir_r) {
+                    corners += 1;
+                }
+            }
+        }
+
+        Self {
+            start,
+            bbox: Rect::bounding(cells.iter().copied()).unwrap(),
+            cells,
+            perimeter,
+            sides: corners,
+            connectivity,
+        }
+    }
+
+    pub fn area(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Whether the region fully surrounds any cells outside of it. `grid`
+    /// is the one the region was labelled on.
+    pub fn has_holes<G: Grid>(&self, grid: &G) -> bool {
+        let mut cells = BitGrid::new(self.bbox.width(), self.bbox.height());
+        for &pos in &self.cells {
+            cells.insert(pos - self.bbox.min);
+        }
+        let inside = |p: Vec2i| {
+            grid.canonical(p)
+                .is_some_and(|p| cells.contains(p - self.bbox.min))
+        };
+
+        // Flood the outside from a ring around the bounding box, whatever
+        // it cannot reach is enclosed.
+        let area = self.bbox.expand(1);
+        let mut outside = BitGrid::new(area.width(), area.height());
+        let mut queue = VecDeque::new();
+        for pos in area
+            .iter()
+            .filter(|&p| !self.bbox.contains(p) && !inside(p))
+        {
+            outside.insert(pos - area.min);
+            queue.push_back(pos);
+        }
+        while let Some(pos) = queue.pop_front() {
+            for dir in self.connectivity.dual().directions() {
+                let next = pos + dir;
+                if area.contains(next) && !inside(next) && outside.insert(next - area.min) {
+                    queue.push_back(next);
+                }
+            }
+        }
+        area.iter()
+            .any(|p| !inside(p) && !outside.contains(p - area.min))
+    }
+}
+
+#[test]
+fn test_regions() {
+    let grid: Grid2D<char> = "AAAA\nABBA\nAAAA\nCCDD\n".parse().unwrap();
+    let regions = Regions::label(&grid, Connectivity::Four);
+    assert_eq!(regions.regions.len(), 4);
+
+    let a = regions.region_at(Vec2i::new(0, 0)).unwrap();
+    assert_eq!((a.area(), a.perimeter, a.sides), (10, 20, 8));
+    assert_eq!(a.bbox, Rect::new(Vec2i::new(0, 0), Vec2i::new(3, 2)));
+    assert!(a.has_holes(&grid));
+
+    let b = regions.region_at(Vec2i::new(1, 1)).unwrap();
+    assert_eq!((b.area(), b.perimeter, b.sides), (2, 6, 4));
+    assert!(!b.has_holes(&grid));
+
+    let grid: Grid2D<char> = "#.\n.#\n".parse().unwrap();
+    let diagonal = Regions::label(&grid, Connectivity::Eight);
+    assert_eq!(diagonal.region_at(Vec2i::new(1, 1)).unwrap().area(), 2);
+    let singletons = Regions::label_by(&grid, Connectivity::Four, |_, _| false);
+    assert_eq!(singletons.regions.len(), 4);
+
+    let torus = Wrapping("AA\nAA\n".parse::<Grid2D<char>>().unwrap());
+    let whole = &Regions::label(&torus, Connectivity::Four).regions[0];
+    assert_eq!((whole.area(), whole.perimeter, whole.sides), (4, 0, 0));
+    assert!(!whole.has_holes(&torus));
+    let ring = Wrapping("ABA\n".parse::<Grid2D<char>>().unwrap());
+    let a = &Regions::label(&ring, Connectivity::Four).regions[0];
+    assert_eq!((a.area(), a.perimeter), (2, 2));
+}
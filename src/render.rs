@@ -0,0 +1,113 @@
+use std::{
+    collections::HashMap,
+    fmt::{self, Display, Formatter},
+    io::{self, IsTerminal},
+};
+
+use crate::{utils::Grid2D, vec::Vec2i, RST};
+
+/// Configurable text form of a [`Grid2D`], an alternative to its `Display`.
+/// By default cells are written next to each other with no separator, as
+/// in the puzzle maps.
+pub struct Renderer<'a, T> {
+    grid: &'a Grid2D<T>,
+    format: Box<dyn Fn(&T) -> String + 'a>,
+    separator: &'a str,
+    width: usize,
+    highlights: HashMap<Vec2i, &'static str>,
+    plain: bool,
+}
+
+impl<'a, T: Display> Renderer<'a, T> {
+    pub fn new(grid: &'a Grid2D<T>) -> Self {
+        Self::with(grid, T::to_string)
+    }
+}
+
+impl<'a, T> Renderer<'a, T> {
+    /// Renders each cell as returned by `format`.
+    pub fn with<F>(grid: &'a Grid2D<T>, format: F) -> Self
+    where
+        F: Fn(&T) -> String + 'a,
+    {
+        Self {
+            grid,
+            format: Box::new(format),
+            separator: "",
+            width: 0,
+            highlights: HashMap::new(),
+            plain: !io::stdout().is_terminal(),
+        }
+    }
+
+    /// Written between the cells of a row.
+    pub fn separator(mut self, separator: &'a str) -> Self {
+        self.separator = separator;
+        self
+    }
+
+    /// Right-aligns each cell to at least `width` characters.
+    pub fn width(mut self, width: usize) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Draws the cells at `positions` in `color`, one of the ANSI colors
+    /// like [`crate::RED`]. Later highlights win where they overlap.
+    pub fn highlight<I>(mut self, positions: I, color: &'static str) -> Self
+    where
+        I: IntoIterator<Item = Vec2i>,
+    {
+        self.highlights
+            .extend(positions.into_iter().map(|pos| (pos, color)));
+        self
+    }
+
+    /// Leaves out the colors. Defaults to whether stdout is not a terminal.
+    pub fn plain(mut self, plain: bool) -> Self {
+        self.plain = plain;
+        self
+    }
+}
+
+impl<T> Display for Renderer<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.grid.rows().enumerate() {
+            for (x, cell) in row.iter().enumerate() {
+                if x > 0 {
+                    f.write_str(self.separator)?;
+                }
+                let text = format!("{:>1$}", (self.format)(cell), self.width);
+                let pos = Vec2i::new(x as i32, y as i32);
+                match self.highlights.get(&pos) {
+                    Some(color) if !self.plain => write!(f, "{color}{text}{RST}")?,
+                    _ => f.write_str(&text)?,
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn test_renderer() {
+    let grid: Grid2D<u32> = Grid2D::parse_with("19\n05\n", |c| c.to_digit(10).ok_or(())).unwrap();
+    let plain = Renderer::new(&grid).plain(true);
+    assert_eq!(plain.to_string(), "19\n05\n");
+
+    let squares = Renderer::with(&grid, |n| (n * n).to_string())
+        .separator("|")
+        .width(2)
+        .plain(true);
+    assert_eq!(squares.to_string(), " 1|81\n 0|25\n");
+
+    let path = Renderer::new(&grid)
+        .highlight([Vec2i::new(1, 0), Vec2i::new(1, 1)], crate::RED)
+        .highlight([Vec2i::new(1, 1)], crate::GRN)
+        .plain(false);
+    assert_eq!(
+        path.to_string(),
+        format!("1{}9{RST}\n0{}5{RST}\n", crate::RED, crate::GRN)
+    );
+}
@@ -0,0 +1,234 @@
+use std::{fmt::Write, time::Duration};
+
+use crate::{Part, Solution, SolveResult, Tag, GRN, RED, RST, YLW};
+
+pub enum Outcome {
+    Answer(String),
+    Error(String),
+    Panic,
+}
+
+pub struct PartReport {
+    pub part: Part,
+    /// Name of the alternative implementation, `None` for the primary one.
+    pub imp: Option<&'static str>,
+    pub outcome: Outcome,
+    pub elapsed: Option<Duration>,
+    /// Part of `elapsed` spent computing values shared with other parts.
+    pub shared_time: Option<Duration>,
+    /// Whether the part got values another part had computed.
+    pub reused_shared: bool,
+}
+
+impl PartReport {
+    pub fn new(
+        part: Part,
+        imp: Option<&'static str>,
+        result: SolveResult,
+        elapsed: Duration,
+    ) -> Self {
+        let outcome = match result {
+            Ok(answer) => Outcome::Answer(answer.to_string()),
+            Err(e) => Outcome::Error(e.to_string()),
+        };
+        Self {
+            part,
+            imp,
+            outcome,
+            elapsed: Some(elapsed),
+            shared_time: None,
+            reused_shared: false,
+        }
+    }
+}
+
+/// Everything the runner found out about one solution.
+pub struct DayReport {
+    pub day: i32,
+    pub title: Option<&'static str>,
+    pub tags: &'static [Tag],
+    /// Set when the parts could not run at all.
+    pub error: Option<String>,
+    pub parse_time: Option<Duration>,
+    pub parts: Vec<PartReport>,
+}
+
+impl DayReport {
+    pub fn new(sol: &dyn Solution) -> Self {
+        Self {
+            day: sol.day_number(),
+            title: sol.title(),
+            tags: sol.tags(),
+            error: None,
+            parse_time: None,
+            parts: Vec::new(),
+        }
+    }
+}
+
+pub fn print_summary(reports: &[DayReport]) {
+    println!("Summary");
+    for r in reports {
+        println!("{}", summary_line(r));
+    }
+    if reports
+        .iter()
+        .flat_map(|r| &r.parts)
+        .any(|p| p.reused_shared)
+    {
+        println!("  * reused state computed by an earlier part");
+    }
+}
+
+/// One row of the summary, the part columns line up across rows.
+fn summary_line(r: &DayReport) -> String {
+    let tags: Vec<_> = r.tags.iter().map(Tag::name).collect();
+    let mut line = format!(
+        "  day {:2}  {:24} {YLW}{:18}{RST}",
+        r.day,
+        r.title.unwrap_or("-"),
+        tags.join(", ")
+    );
+
+    if let Some(e) = &r.error {
+        write!(line, " {RED}{e}{RST}").unwrap();
+    }
+    match r.parse_time {
+        Some(t) => write!(line, " parse {:>10}", format!("{t:.2?}")).unwrap(),
+        None => write!(line, " {:16}", "").unwrap(),
+    }
+    for p in &r.parts {
+        let status = match p.outcome {
+            Outcome::Answer(_) => format!("{GRN}ok{RST}"),
+            Outcome::Error(_) => format!("{RED}error{RST}"),
+            Outcome::Panic => format!("{RED}panic{RST}"),
+        };
+        let mut time = p.elapsed.map(|t| format!("{t:.2?}")).unwrap_or_default();
+        if p.reused_shared {
+            time.push('*');
+        }
+        let name = match p.imp {
+            Some(imp) => format!("{}/{imp}", p.part),
+            None => p.part.to_string(),
+        };
+        write!(line, " | {name} {status} {time:>10}").unwrap();
+    }
+    line
+}
+
+pub fn to_json(reports: &[DayReport]) -> String {
+    let mut out = String::from("[\n");
+    for (i, r) in reports.iter().enumerate() {
+        let tags: Vec<_> = r.tags.iter().map(|t| json_str(t.name())).collect();
+        write!(
+            out,
+            "  {{\"day\": {}, \"title\": {}, \"tags\": [{}], \"error\": {}, \"parse_ms\": {}, \"parts\": [",
+            r.day,
+            r.title.map_or("null".to_owned(), json_str),
+            tags.join(", "),
+            r.error.as_deref().map_or("null".to_owned(), json_str),
+            json_ms(r.parse_time),
+        )
+        .unwrap();
+
+        for (j, p) in r.parts.iter().enumerate() {
+            let (status, value) = match &p.outcome {
+                Outcome::Answer(a) => ("ok", json_str(a)),
+                Outcome::Error(e) => ("error", json_str(e)),
+                Outcome::Panic => ("panic", "null".to_owned()),
+            };
+            let key = if status == "ok" { "answer" } else { "error" };
+            let sep = if j > 0 { ", " } else { "" };
+            write!(
+                out,
+                "{sep}{{\"part\": \"{}\", \"impl\": {}, \"status\": \"{status}\", \"{key}\": {value}, \"ms\": {}, \"shared_ms\": {}, \"reused_shared\": {}}}",
+                p.part,
+                p.imp.map_or("null".to_owned(), json_str),
+                json_ms(p.elapsed),
+                json_ms(p.shared_time),
+                p.reused_shared
+            )
+            .unwrap();
+        }
+
+        let sep = if i + 1 < reports.len() { "," } else { "" };
+        writeln!(out, "]}}{sep}").unwrap();
+    }
+    out.push_str("]\n");
+    out
+}
+
+fn json_ms(time: Option<Duration>) -> String {
+    match time {
+        Some(t) => format!("{:.3}", t.as_secs_f64() * 1000.0),
+        None => "null".to_owned(),
+    }
+}
+
+fn json_str(s: &str) -> String {
+    let mut out = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            c if (c as u32) < 0x20 => write!(out, "\\u{:04x}", c as u32).unwrap(),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[test]
+fn test_summary_columns() {
+    let part = |elapsed| PartReport::new(Part::One, None, Ok(1.into()), elapsed);
+    let day = |parse_time, tags| DayReport {
+        day: 1,
+        title: Some("Historian Hysteria"),
+        tags,
+        error: None,
+        parse_time,
+        parts: vec![part(Duration::from_micros(5))],
+    };
+    let rows = [
+        day(None, &[Tag::Parsing, Tag::Dp]),
+        day(Some(Duration::from_nanos(6470)), &[Tag::Grid]),
+        day(Some(Duration::from_secs(12)), &[]),
+    ];
+    let offsets: Vec<_> = rows
+        .iter()
+        .map(|r| {
+            let line = summary_line(r);
+            line.find("| one").map(|i| line[..i].chars().count())
+        })
+        .collect();
+    assert!(offsets[0].is_some());
+    assert!(offsets.iter().all(|&o| o == offsets[0]), "{offsets:?}");
+}
+
+#[test]
+fn test_json() {
+    let report = DayReport {
+        day: 3,
+        title: Some("Mull It Over"),
+        tags: &[Tag::Parsing],
+        error: None,
+        parse_time: None,
+        parts: vec![PartReport {
+            part: Part::One,
+            imp: None,
+            outcome: Outcome::Error("bad \"mul\"\n".to_owned()),
+            elapsed: None,
+            shared_time: None,
+            reused_shared: false,
+        }],
+    };
+    assert_eq!(
+        to_json(&[report]),
+        "[\n  {\"day\": 3, \"title\": \"Mull It Over\", \"tags\": [\"parsing\"], \"error\": null, \
+         \"parse_ms\": null, \"parts\": [{\"part\": \"one\", \"impl\": null, \"status\": \"error\", \
+         \"error\": \"bad \\\"mul\\\"\\n\", \"ms\": null, \"shared_ms\": null, \
+         \"reused_shared\": false}]}\n]\n"
+    );
+}
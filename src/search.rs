@@ -0,0 +1,310 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, VecDeque},
+};
+
+use crate::{grid::Grid, vec::Vec2i};
+
+#[cfg(test)]
+use crate::{grid::Wrapping, utils::Grid2D};
+
+/// Shortest paths over the 4-neighbourhood of a [`Grid`], from any of the
+/// start positions. Moves are judged by a closure receiving both ends of the
+/// step, `(from, &from_cell, to, &to_cell)`.
+///
+/// On grids without [`Grid::bounds`], like [`crate::grid::Tiled`], the
+/// search only ends by reaching its goal, so it needs one and panics
+/// otherwise. An unreachable goal there searches forever.
+///
+/// ```ignore
+/// let paths = Search::new(&grid).start(start).goal(end).dijkstra(|_, _, _, &c| {
+///     (c != '#').then_some(1)
+/// });
+/// ```
+pub struct Search<'a, G> {
+    grid: &'a G,
+    starts: Vec<Vec2i>,
+    goal: Option<Vec2i>,
+}
+
+/// Everything a [`Search`] reached. Searches with a goal stop once the goal's
+/// distance is known, so other distances may be missing or not minimal.
+pub struct Paths {
+    dist: HashMap<Vec2i, u64>,
+    prev: HashMap<Vec2i, Vec2i>,
+    goal: Option<Vec2i>,
+}
+
+impl<'a, G: Grid> Search<'a, G> {
+    pub fn new(grid: &'a G) -> Self {
+        Self {
+            grid,
+            starts: Vec::new(),
+            goal: None,
+        }
+    }
+
+    pub fn start(mut self, pos: Vec2i) -> Self {
+        self.starts.push(pos);
+        self
+    }
+
+    pub fn starts(mut self, starts: impl IntoIterator<Item = Vec2i>) -> Self {
+        self.starts.extend(starts);
+        self
+    }
+
+    pub fn goal(mut self, pos: Vec2i) -> Self {
+        self.goal = Some(pos);
+        self
+    }
+
+    /// Every step costs 1, `passable` tells which steps can be taken.
+    pub fn bfs<F>(self, mut passable: F) -> Paths
+    where
+        F: FnMut(Vec2i, &G::Cell, Vec2i, &G::Cell) -> bool,
+    {
+        let mut paths = self.paths();
+        let mut queue: VecDeque<_> = paths.dist.keys().copied().collect();
+        while let Some(pos) = queue.pop_front() {
+            if Some(pos) == paths.goal {
+                break;
+            }
+            let d = paths.dist[&pos];
+            let from = self.cell(pos);
+            for (next, cell) in self.grid.neighbors4(pos) {
+                if !paths.dist.contains_key(&next) && passable(pos, from, next, cell) {
+                    paths.dist.insert(next, d + 1);
+                    paths.prev.insert(next, pos);
+                    queue.push_back(next);
+                }
+            }
+        }
+        paths
+    }
+
+    /// For steps costing either 0 or 1, `None` marks impassable steps.
+    pub fn zero_one_bfs<F>(self, mut cost: F) -> Paths
+    where
+        F: FnMut(Vec2i, &G::Cell, Vec2i, &G::Cell) -> Option<u32>,
+    {
+        let mut paths = self.paths();
+        let mut queue: VecDeque<_> = paths.dist.keys().map(|&p| (0, p)).collect();
+        while let Some((d, pos)) = queue.pop_front() {
+            if d > paths.dist[&pos] {
+                continue;
+            }
+            if Some(pos) == paths.goal {
+                break;
+            }
+            let from = self.cell(pos);
+            for (next, cell) in self.grid.neighbors4(pos) {
+                let Some(c) = cost(pos, from, next, cell) else {
+                    continue;
+                };
+                assert!(c <= 1, "0-1 BFS got a step costing {c}");
+                if paths.relax(pos, next, d + c as u64) {
+                    match c {
+                        0 => queue.push_front((d, next)),
+                        _ => queue.push_back((d + 1, next)),
+                    }
+                }
+            }
+        }
+        paths
+    }
+
+    /// `cost` gives the cost of a step, `None` marks impassable steps.
+    pub fn dijkstra<F>(self, cost: F) -> Paths
+    where
+        F: FnMut(Vec2i, &G::Cell, Vec2i, &G::Cell) -> Option<u32>,
+    {
+        self.best_first(cost, |_| 0)
+    }
+
+    /// Dijkstra guided by the Manhattan distance to the canonical goal, so
+    /// every step has to cost at least 1. Along axes where the grid wraps
+    /// the distance is measured the shorter way round. Panics without a
+    /// goal.
+    pub fn a_star<F>(self, cost: F) -> Paths
+    where
+        F: FnMut(Vec2i, &G::Cell, Vec2i, &G::Cell) -> Option<u32>,
+    {
+        let goal = self.goal.expect("A* needs a goal");
+        let goal = self.grid.canonical(goal).unwrap_or(goal);
+
+        // A bounded grid wraps along an axis if a cell a whole grid size
+        // away from the goal still exists.
+        let size = self
+            .grid
+            .bounds()
+            .map(|b| Vec2i::new(b.width(), b.height()));
+        let wraps = |step: Vec2i| self.grid.canonical(goal + step).is_some();
+        let (wrap_x, wrap_y) = size.map_or((false, false), |s| {
+            (wraps(Vec2i::new(s.x, 0)), wraps(Vec2i::new(0, s.y)))
+        });
+        let size = size.unwrap_or(Vec2i::new(0, 0));
+        let axis = |d: i32, size: i32, wraps: bool| {
+            let d = d.abs();
+            if wraps {
+                d.min(size - d)
+            } else {
+                d
+            }
+        };
+        self.best_first(cost, move |pos| {
+            let d = pos - goal;
+            (axis(d.x, size.x, wrap_x) + axis(d.y, size.y, wrap_y)) as u64
+        })
+    }
+
+    fn best_first<F, H>(self, mut cost: F, heuristic: H) -> Paths
+    where
+        F: FnMut(Vec2i, &G::Cell, Vec2i, &G::Cell) -> Option<u32>,
+        H: Fn(Vec2i) -> u64,
+    {
+        let mut paths = self.paths();
+        let mut heap: BinaryHeap<_> = (paths.dist.keys())
+            .map(|&p| Reverse((heuristic(p), 0, p.x, p.y)))
+            .collect();
+        while let Some(Reverse((_, d, x, y))) = heap.pop() {
+            let pos = Vec2i::new(x, y);
+            if d > paths.dist[&pos] {
+                continue;
+            }
+            if Some(pos) == paths.goal {
+                break;
+            }
+            let from = self.cell(pos);
+            for (next, cell) in self.grid.neighbors4(pos) {
+                let Some(c) = cost(pos, from, next, cell) else {
+                    continue;
+                };
+                let next_d = d + c as u64;
+                if paths.relax(pos, next, next_d) {
+                    heap.push(Reverse((next_d + heuristic(next), next_d, next.x, next.y)));
+                }
+            }
+        }
+        paths
+    }
+
+    /// Starts and goal are moved to their canonical positions, starts
+    /// without a cell are dropped.
+    fn paths(&self) -> Paths {
+        assert!(
+            self.goal.is_some() || self.grid.bounds().is_some(),
+            "searching an unbounded grid needs a goal"
+        );
+        let starts = self.starts.iter().filter_map(|&p| self.grid.canonical(p));
+        Paths {
+            dist: starts.map(|p| (p, 0)).collect(),
+            prev: HashMap::new(),
+            goal: self.goal.and_then(|p| self.grid.canonical(p)),
+        }
+    }
+
+    fn cell(&self, pos: Vec2i) -> &'a G::Cell {
+        self.grid.get(pos).expect("reached positions have cells")
+    }
+}
+
+impl Paths {
+    /// Records `to` as reached through `from` if that is shorter.
+    fn relax(&mut self, from: Vec2i, to: Vec2i, dist: u64) -> bool {
+        if self.dist.get(&to).is_some_and(|&d| d <= dist) {
+            return false;
+        }
+        self.dist.insert(to, dist);
+        self.prev.insert(to, from);
+        true
+    }
+
+    pub fn distance(&self, pos: Vec2i) -> Option<u64> {
+        self.dist.get(&pos).copied()
+    }
+
+    /// Distances of all reached positions, the starts included.
+    pub fn distances(&self) -> &HashMap<Vec2i, u64> {
+        &self.dist
+    }
+
+    pub fn predecessor(&self, pos: Vec2i) -> Option<Vec2i> {
+        self.prev.get(&pos).copied()
+    }
+
+    /// Positions from a start to `pos`, both included.
+    pub fn path_to(&self, pos: Vec2i) -> Option<Vec<Vec2i>> {
+        self.dist.get(&pos)?;
+        let mut path = vec![pos];
+        while let Some(prev) = self.predecessor(*path.last().unwrap()) {
+            path.push(prev);
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    pub fn goal_distance(&self) -> Option<u64> {
+        self.distance(self.goal?)
+    }
+
+    pub fn goal_path(&self) -> Option<Vec<Vec2i>> {
+        self.path_to(self.goal?)
+    }
+}
+
+#[test]
+fn test_strategies() {
+    let grid: Grid2D<char> = "S..#....\n.#.#.##.\n.#...#E.\n".parse().unwrap();
+    let start = Vec2i::new(0, 0);
+    let goal = Vec2i::new(6, 2);
+    let cost = |_, _: &char, _, &c: &char| (c != '#').then_some(1);
+
+    let bfs = Search::new(&grid)
+        .start(start)
+        .goal(goal)
+        .bfs(|_, _, _, &c| c != '#');
+    let zero_one = Search::new(&grid)
+        .start(start)
+        .goal(goal)
+        .zero_one_bfs(cost);
+    let dijkstra = Search::new(&grid).start(start).goal(goal).dijkstra(cost);
+    let a_star = Search::new(&grid).start(start).goal(goal).a_star(cost);
+    for paths in [&bfs, &zero_one, &dijkstra, &a_star] {
+        assert_eq!(paths.goal_distance(), Some(14));
+        let path = paths.goal_path().unwrap();
+        assert_eq!((path[0], path[14]), (start, goal));
+    }
+
+    let all = Search::new(&grid).start(start).bfs(|_, _, _, &c| c != '#');
+    assert_eq!(all.distance(Vec2i::new(7, 0)), Some(11));
+
+    // Wrapping round makes the goal 4 steps away, the goal is given
+    // outside the grid on purpose.
+    let torus = Wrapping(
+        "#....#\n...##.\n.#....\n......\n"
+            .parse::<Grid2D<char>>()
+            .unwrap(),
+    );
+    let far_goal = Vec2i::new(11, 2);
+    let bfs = Search::new(&torus)
+        .start(Vec2i::new(1, 0))
+        .goal(far_goal)
+        .bfs(|_, _, _, &c| c != '#');
+    let a_star = Search::new(&torus)
+        .start(Vec2i::new(1, 0))
+        .goal(far_goal)
+        .a_star(cost);
+    assert_eq!(bfs.goal_distance(), Some(4));
+    assert_eq!(a_star.goal_distance(), Some(4));
+
+    let tiled = crate::grid::Tiled(grid);
+    let far = Search::new(&tiled)
+        .start(start)
+        .goal(Vec2i::new(6, 5))
+        .bfs(|_, _, _, &c| c != '#');
+    assert_eq!(far.goal_distance(), Some(13));
+    let endless =
+        std::panic::catch_unwind(|| Search::new(&tiled).start(start).bfs(|_, _, _, _| true));
+    assert!(endless.is_err());
+}
@@ -1,15 +1,22 @@
 use multimap::MultiMap;
 use std::fmt::{Display, Formatter};
 use std::hash::{BuildHasher, Hash};
+use std::iter;
 use std::mem::{transmute_copy, MaybeUninit};
 use std::ops::{Index, IndexMut};
-use std::slice::{Iter, IterMut};
+use std::slice::{ChunksExact, ChunksExactMut, Iter, IterMut};
 use std::str::FromStr;
 
-use crate::vec::Vec2i;
+use crate::{
+    vec::{Rect, Vec2i},
+    SolveError,
+};
 
 pub trait DestructIterator<T> {
     fn destruct<const N: usize>(self) -> [T; N];
+
+    /// Like `destruct`, but returns `None` unless there are exactly `N` items.
+    fn try_destruct<const N: usize>(self) -> Option<[T; N]>;
 }
 impl<T, I: Iterator<Item = T>> DestructIterator<T> for I {
     fn destruct<const N: usize>(mut self) -> [T; N] {
@@ -32,6 +39,10 @@ impl<T, I: Iterator<Item = T>> DestructIterator<T> for I {
 
         unsafe { transmute_copy(&slice) }
     }
+
+    fn try_destruct<const N: usize>(self) -> Option<[T; N]> {
+        self.collect::<Vec<_>>().try_into().ok()
+    }
 }
 
 pub trait MultiMapContains<K, V> {
@@ -75,7 +86,7 @@ impl<I: Iterator> Iterator for OptionIter<I> {
     }
 }
 
-#[derive(Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Grid2D<T> {
     width: i32,
     height: i32,
@@ -140,6 +151,126 @@ impl<T> Grid2D<T> {
         self.iter_indices().map(|p| p.into())
     }
 
+    pub fn rows(&self) -> ChunksExact<'_, T> {
+        self.data.chunks_exact(self.width.max(1) as usize)
+    }
+
+    pub fn rows_mut(&mut self) -> ChunksExactMut<'_, T> {
+        self.data.chunks_exact_mut(self.width.max(1) as usize)
+    }
+
+    /// Columns from left to right, each from top to bottom.
+    pub fn cols(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(move |x| self.ray(Vec2i::new(x, 0), Vec2i::new(0, 1)))
+    }
+
+    /// Lines running down and to the right, from the bottom left corner
+    /// to the top right one.
+    pub fn diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let (w, h) = (self.width, self.height);
+        (0..(w + h - 1).max(0)).map(move |k| {
+            let start = Vec2i::new((k - (h - 1)).max(0), (h - 1 - k).max(0));
+            self.ray(start, Vec2i::new(1, 1))
+        })
+    }
+
+    /// Lines running down and to the left, from the top left corner to the
+    /// bottom right one.
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let (w, h) = (self.width, self.height);
+        (0..(w + h - 1).max(0)).map(move |k| {
+            let start = Vec2i::new(k.min(w - 1), (k - (w - 1)).max(0));
+            self.ray(start, Vec2i::new(-1, 1))
+        })
+    }
+
+    /// Cells from `start` on in steps of `dir`, until leaving the grid.
+    pub fn ray(&self, start: Vec2i, dir: Vec2i) -> impl Iterator<Item = &T> {
+        let mut pos = start;
+        iter::from_fn(move || {
+            let cell = self.get(pos)?;
+            pos = pos + dir;
+            Some(cell)
+        })
+    }
+
+    /// Borrows the cells within `rect`, which has to lie inside the grid
+    /// with `min` no further right or down than `max`.
+    pub fn view(&self, rect: Rect) -> SubGrid<'_, T> {
+        let bounds = Rect::new(
+            Vec2i::new(0, 0),
+            Vec2i::new(self.width - 1, self.height - 1),
+        );
+        assert!(
+            rect.min.x <= rect.max.x && rect.min.y <= rect.max.y,
+            "{rect:?} is inverted"
+        );
+        assert!(
+            bounds.contains(rect.min) && bounds.contains(rect.max),
+            "{rect:?} is outside the grid dimensions ({}x{})",
+            self.width,
+            self.height
+        );
+        SubGrid { grid: self, rect }
+    }
+
+    /// A copy of the grid with `transform` applied, see
+    /// [`Transform::map_back`] for finding where its cells came from.
+    pub fn transform(&self, transform: Transform) -> Self
+    where
+        T: Clone,
+    {
+        let (width, height) = transform.size(self.width, self.height);
+        let data = (0..width * height)
+            .map(|i| {
+                let pos = Vec2i::new(i % width, i / width);
+                self[transform.map_back(pos, self.width, self.height)].clone()
+            })
+            .collect();
+        Self {
+            width,
+            height,
+            data,
+        }
+    }
+
+    pub fn rotate_cw(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.transform(Transform::RotateCw)
+    }
+
+    pub fn rotate_ccw(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.transform(Transform::RotateCcw)
+    }
+
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.transform(Transform::Transpose)
+    }
+
+    /// Mirrors left and right.
+    pub fn flip_horizontal(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.transform(Transform::FlipHorizontal)
+    }
+
+    /// Mirrors top and bottom.
+    pub fn flip_vertical(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.transform(Transform::FlipVertical)
+    }
+
     pub fn map<R, F>(self, mut f: F) -> Grid2D<R>
     where
         F: FnMut(T) -> R,
@@ -168,33 +299,281 @@ impl<T> Grid2D<T> {
     }
 }
 
-impl FromStr for Grid2D<char> {
-    type Err = ();
-    fn from_str(input: &str) -> Result<Self, Self::Err> {
-        let lines = input.lines();
+impl<T> Grid2D<T> {
+    /// Parses a grid with one cell per character, `f` turns the characters
+    /// into cells. Blank lines before the grid and trailing whitespace are
+    /// ignored, the grid ends at the first blank line after it. All rows
+    /// have to be equally wide.
+    pub fn parse_with<E, F>(input: &str, f: F) -> Result<Self, GridParseError<E>>
+    where
+        F: FnMut(char) -> Result<T, E>,
+    {
+        Self::parse_rows(input, f, None)
+    }
 
-        let width = lines.clone().next().unwrap().len() as i32;
-        let height = lines.clone().filter(|l| l.trim().len() != 0).count() as i32;
-        let mut data = Vec::new();
-        data.reserve((width * height) as _);
+    /// Like [`Grid2D::parse_with`], but rows shorter than the widest one are
+    /// padded with `fill`.
+    pub fn parse_padded<E, F>(input: &str, fill: T, f: F) -> Result<Self, GridParseError<E>>
+    where
+        T: Clone,
+        F: FnMut(char) -> Result<T, E>,
+    {
+        Self::parse_rows(input, f, Some(&|| fill.clone()))
+    }
 
-        for l in lines {
-            if data.len() as i32 == width * height {
-                break;
+    fn parse_rows<E, F>(
+        input: &str,
+        mut f: F,
+        fill: Option<&dyn Fn() -> T>,
+    ) -> Result<Self, GridParseError<E>>
+    where
+        F: FnMut(char) -> Result<T, E>,
+    {
+        // (line number, row) of the rows making up the grid
+        let rows: Vec<_> = input
+            .lines()
+            .enumerate()
+            .map(|(i, l)| (i + 1, l.trim_end()))
+            .skip_while(|(_, l)| l.is_empty())
+            .take_while(|(_, l)| !l.is_empty())
+            .collect();
+        let widths = rows.iter().map(|(_, l)| l.chars().count());
+        let width = match fill {
+            Some(_) => widths.max(),
+            None => widths.clone().next(),
+        }
+        .ok_or(GridParseError::Empty)?;
+
+        let mut data = Vec::with_capacity(width * rows.len());
+        for (y, &(line, row)) in rows.iter().enumerate() {
+            let mut actual = 0;
+            for (x, c) in row.chars().enumerate() {
+                let cell = f(c).map_err(|error| {
+                    GridParseError::Cell(CellError {
+                        x: x as i32,
+                        y: y as i32,
+                        line,
+                        ch: c,
+                        error,
+                    })
+                })?;
+                data.push(cell);
+                actual += 1;
             }
-            assert_eq!(l.len(), width as usize);
 
-            for c in l.chars() {
-                data.push(c);
+            match fill {
+                Some(fill) => data.extend((actual..width).map(|_| fill())),
+                None if actual != width => {
+                    return Err(GridParseError::Ragged {
+                        line,
+                        expected: width,
+                        actual,
+                    })
+                }
+                None => {}
             }
         }
         Ok(Self {
-            width,
-            height,
+            width: width as i32,
+            height: rows.len() as i32,
             data,
         })
     }
 }
+
+/// Part of a [`Grid2D`] made by [`Grid2D::view`], indexed relative to its
+/// top left corner.
+#[derive(Clone, Copy)]
+pub struct SubGrid<'a, T> {
+    grid: &'a Grid2D<T>,
+    rect: Rect,
+}
+
+impl<'a, T> SubGrid<'a, T> {
+    pub fn width(&self) -> i32 {
+        self.rect.width()
+    }
+
+    pub fn height(&self) -> i32 {
+        self.rect.height()
+    }
+
+    /// The area of the underlying grid this view covers.
+    pub fn rect(&self) -> Rect {
+        self.rect
+    }
+
+    pub fn get<I: Into<(i32, i32)>>(&self, idx: I) -> Option<&'a T> {
+        let (x, y) = idx.into();
+        if x >= 0 && x < self.width() && y >= 0 && y < self.height() {
+            self.grid.get(self.rect.min + Vec2i::new(x, y))
+        } else {
+            None
+        }
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &'a [T]> {
+        let (grid, rect) = (self.grid, self.rect);
+        (rect.min.y..=rect.max.y).map(move |y| {
+            let start = (y * grid.width + rect.min.x) as usize;
+            &grid.data[start..start + rect.width() as usize]
+        })
+    }
+
+    pub fn iter_vec2(&self) -> impl Iterator<Item = Vec2i> {
+        let min = self.rect.min;
+        self.rect.iter().map(move |p| p - min)
+    }
+}
+
+impl<T, I: Into<(i32, i32)>> Index<I> for SubGrid<'_, T> {
+    type Output = T;
+    fn index(&self, index: I) -> &Self::Output {
+        let (x, y) = index.into();
+        self.get((x, y)).unwrap_or_else(|| {
+            panic!(
+                "Index ({}, {}) is outside the view dimensions ({}x{})",
+                x,
+                y,
+                self.width(),
+                self.height()
+            )
+        })
+    }
+}
+
+/// A rotation or reflection of a whole grid, y pointing down.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Transform {
+    RotateCw,
+    RotateCcw,
+    Transpose,
+    FlipHorizontal,
+    FlipVertical,
+}
+
+impl Transform {
+    pub fn inverse(self) -> Self {
+        match self {
+            Transform::RotateCw => Transform::RotateCcw,
+            Transform::RotateCcw => Transform::RotateCw,
+            t => t,
+        }
+    }
+
+    /// Size of a `width` x `height` grid after the transform.
+    pub fn size(self, width: i32, height: i32) -> (i32, i32) {
+        match self {
+            Transform::RotateCw | Transform::RotateCcw | Transform::Transpose => (height, width),
+            Transform::FlipHorizontal | Transform::FlipVertical => (width, height),
+        }
+    }
+
+    /// Where `pos` of a `width` x `height` grid ends up.
+    pub fn apply(self, pos: Vec2i, width: i32, height: i32) -> Vec2i {
+        let Vec2i { x, y } = pos;
+        match self {
+            Transform::RotateCw => Vec2i::new(height - 1 - y, x),
+            Transform::RotateCcw => Vec2i::new(y, width - 1 - x),
+            Transform::Transpose => Vec2i::new(y, x),
+            Transform::FlipHorizontal => Vec2i::new(width - 1 - x, y),
+            Transform::FlipVertical => Vec2i::new(x, height - 1 - y),
+        }
+    }
+
+    /// Maps `pos` of the transformed grid back to the original
+    /// `width` x `height` grid.
+    pub fn map_back(self, pos: Vec2i, width: i32, height: i32) -> Vec2i {
+        let (width, height) = self.size(width, height);
+        self.inverse().apply(pos, width, height)
+    }
+}
+
+/// Why [`Grid2D::parse_with`] failed, `line` numbers are 1-based and count
+/// every line of the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GridParseError<E> {
+    Empty,
+    /// A row whose width differs from the first row's.
+    Ragged {
+        line: usize,
+        expected: usize,
+        actual: usize,
+    },
+    Cell(CellError<E>),
+}
+
+impl<E> GridParseError<E> {
+    /// For errors of grids parsed from the input after its first `n` lines.
+    pub(crate) fn shift_lines(mut self, n: usize) -> Self {
+        match &mut self {
+            GridParseError::Empty => {}
+            GridParseError::Ragged { line, .. } => *line += n,
+            GridParseError::Cell(e) => e.line += n,
+        }
+        self
+    }
+}
+
+/// A character the cell mapper rejected, at grid position (`x`, `y`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CellError<E> {
+    pub x: i32,
+    pub y: i32,
+    pub line: usize,
+    pub ch: char,
+    pub error: E,
+}
+
+impl<E: Display> GridParseError<E> {
+    /// Converts to a [`SolveError`] pointing at the problem in `input`.
+    pub fn locate(&self, input: &str) -> SolveError {
+        match self {
+            GridParseError::Empty => SolveError::new("the grid is empty"),
+            GridParseError::Ragged {
+                line,
+                expected,
+                actual,
+            } => SolveError::new(format!("row is {actual} cells wide, expected {expected}"))
+                .at_line(input, *line, expected.min(actual) + 1),
+            GridParseError::Cell(e) => SolveError::new(format!(
+                "invalid cell {:?}: {}",
+                e.ch, e.error
+            ))
+            .at_line(input, e.line, e.x as usize + 1),
+        }
+    }
+}
+
+impl<E: Display> Display for GridParseError<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GridParseError::Empty => f.write_str("the grid is empty"),
+            GridParseError::Ragged {
+                line,
+                expected,
+                actual,
+            } => write!(f, "line {line} is {actual} cells wide, expected {expected}"),
+            GridParseError::Cell(e) => write!(
+                f,
+                "invalid cell {:?} at ({}, {}): {}",
+                e.ch, e.x, e.y, e.error
+            ),
+        }
+    }
+}
+
+impl<E: Display + std::fmt::Debug> std::error::Error for GridParseError<E> {}
+
+impl<T: TryFrom<char>> FromStr for Grid2D<T> {
+    type Err = GridParseError<T::Error>;
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        Self::parse_with(input, T::try_from)
+    }
+}
+
+/// Each cell followed by a space, see [`crate::render::Renderer`] for other
+/// layouts.
 impl<T: Display> Display for Grid2D<T> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         for y in 0..self.height {
@@ -305,6 +684,78 @@ where
     }
 }
 
+#[test]
+fn test_parse_with() {
+    let grid = Grid2D::parse_with("12\n34\n", |c| c.to_digit(10).ok_or("not a digit")).unwrap();
+    assert_eq!(grid[(1, 1)], 4);
+
+    let err = Grid2D::parse_with("12\n3x\n", |c| c.to_digit(10).ok_or("not a digit"));
+    assert_eq!(
+        err.unwrap_err().to_string(),
+        "invalid cell 'x' at (1, 1): not a digit"
+    );
+
+    let grid: Grid2D<u8> = "ab\n".parse().unwrap();
+    assert_eq!(grid[(1, 0)], b'b');
+}
+
+#[test]
+fn test_parse_tolerant() {
+    let grid: Grid2D<char> = "\r\n\nab \r\nçd\r\n\n".parse().unwrap();
+    assert_eq!((grid.width(), grid.height()), (2, 2));
+    assert_eq!(grid[(0, 1)], 'ç');
+
+    let err = "ab\nc\n".parse::<Grid2D<char>>().unwrap_err();
+    let expected = GridParseError::Ragged {
+        line: 2,
+        expected: 2,
+        actual: 1,
+    };
+    assert_eq!(err, expected);
+
+    let grid = Grid2D::parse_padded("ab\nc\n", '.', Ok::<_, ()>).unwrap();
+    assert_eq!(grid[(1, 1)], '.');
+    assert!("\n\n".parse::<Grid2D<char>>().is_err());
+}
+
+#[test]
+fn test_transform() {
+    let grid: Grid2D<char> = "ab\ncd\nef\n".parse().unwrap();
+    let show = |g: Grid2D<char>| g.to_string().replace(' ', "");
+    assert_eq!(show(grid.rotate_cw()), "eca\nfdb\n");
+    assert_eq!(show(grid.rotate_ccw()), "bdf\nace\n");
+    assert_eq!(show(grid.transpose()), "ace\nbdf\n");
+    assert_eq!(show(grid.flip_horizontal()), "ba\ndc\nfe\n");
+    assert_eq!(show(grid.flip_vertical()), "ef\ncd\nab\n");
+
+    let rotated = grid.rotate_cw();
+    let pos = rotated.index_of(|&c| c == 'f').unwrap().into();
+    let back = Transform::RotateCw.map_back(pos, grid.width(), grid.height());
+    assert_eq!(grid[back], 'f');
+    assert_eq!(Transform::RotateCw.apply(back, 2, 3), pos);
+}
+
+#[test]
+fn test_lines() {
+    let grid: Grid2D<char> = "abc\ndef\n".parse().unwrap();
+    let collect = |lines: Vec<String>| lines.join(" ");
+    let rows = grid.rows().map(|r| r.iter().collect()).collect();
+    assert_eq!(collect(rows), "abc def");
+    let cols = grid.cols().map(|c| c.collect()).collect();
+    assert_eq!(collect(cols), "ad be cf");
+    let diagonals = grid.diagonals().map(|d| d.collect()).collect();
+    assert_eq!(collect(diagonals), "d ae bf c");
+    let anti_diagonals = grid.anti_diagonals().map(|d| d.collect()).collect();
+    assert_eq!(collect(anti_diagonals), "a bd ce f");
+
+    let view = grid.view(Rect::new(Vec2i::new(1, 0), Vec2i::new(2, 1)));
+    assert_eq!((view[(0, 0)], view[(1, 1)]), ('b', 'f'));
+    assert_eq!(view.get((2, 0)), None);
+    assert_eq!(view.rows().collect::<Vec<_>>(), [['b', 'c'], ['e', 'f']]);
+    let inverted = Rect::new(Vec2i::new(2, 0), Vec2i::new(1, 1));
+    assert!(std::panic::catch_unwind(|| grid.view(inverted).width()).is_err());
+}
+
 #[test]
 fn test_pairs() {
     let a = [1];
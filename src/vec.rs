@@ -166,3 +166,56 @@ impl fmt::Display for Vec2i {
         f.debug_tuple("").field(&self.x).field(&self.y).finish()
     }
 }
+
+/// Axis-aligned rectangle of grid positions, `min` and `max` both included.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct Rect {
+    pub min: Vec2i,
+    pub max: Vec2i,
+}
+
+impl Rect {
+    pub fn new(min: Vec2i, max: Vec2i) -> Self {
+        Self { min, max }
+    }
+
+    /// Smallest rect containing all `points`, `None` if there are none.
+    pub fn bounding(points: impl IntoIterator<Item = Vec2i>) -> Option<Self> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        Some(points.fold(Self::new(first, first), |r, p| Self {
+            min: Vec2i::new(r.min.x.min(p.x), r.min.y.min(p.y)),
+            max: Vec2i::new(r.max.x.max(p.x), r.max.y.max(p.y)),
+        }))
+    }
+
+    pub fn width(&self) -> i32 {
+        self.max.x - self.min.x + 1
+    }
+
+    pub fn height(&self) -> i32 {
+        self.max.y - self.min.y + 1
+    }
+
+    pub fn area(&self) -> i32 {
+        self.width() * self.height()
+    }
+
+    pub fn contains(&self, p: Vec2i) -> bool {
+        (self.min.x..=self.max.x).contains(&p.x) && (self.min.y..=self.max.y).contains(&p.y)
+    }
+
+    /// Grows the rectangle by `amt` on every side.
+    pub fn expand(&self, amt: i32) -> Self {
+        Self {
+            min: Vec2i::new(self.min.x - amt, self.min.y - amt),
+            max: Vec2i::new(self.max.x + amt, self.max.y + amt),
+        }
+    }
+
+    /// All positions, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = Vec2i> {
+        let Self { min, max } = *self;
+        (min.y..=max.y).flat_map(move |y| (min.x..=max.x).map(move |x| Vec2i::new(x, y)))
+    }
+}